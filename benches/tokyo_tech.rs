@@ -0,0 +1,113 @@
+//! src/bin の tokyo-tech-2020-2-1, tokyo-tech-2020-2-2 と同じグレブナー基底の計算時間を測る
+//!
+//!   cargo bench --bench tokyo_tech
+//!
+//! 各問題を何度か解き、最小と中央の時間を出す。回数は引数で変えられる
+//! (cargo bench --bench tokyo_tech -- 10)。
+extern crate groebner_basis;
+
+use groebner_basis::groebner_basis::{compute_groebner_basis, eliminate};
+use groebner_basis::monomial::MonomialOrder;
+use groebner_basis::polynomial::{Polynomial, PolynomialRing};
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn parser(ring: &Arc<PolynomialRing>) -> impl Fn(&str) -> Polynomial + '_ {
+    move |s| ring.parse(s).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// tokyo-tech-2020-2-1: 正三角形の条件を零点定理で確かめる 4 つの基底
+fn tokyo_tech_2020_2_1() -> Vec<Vec<Polynomial>> {
+    let ring = PolynomialRing::new(
+        &["a1", "a2", "b1", "b2", "c1", "c2", "v", "y"],
+        MonomialOrder::Grlex,
+    );
+    let parse = parser(&ring);
+
+    let f3 = parse("-1 + a2 b1 v - a1 b2 v - a2 c1 v + b2 c1 v + a1 c2 v - b1 c2 v");
+    let f1 = parse(
+        "a1^2 - a2^2 - a1 b1 + b1^2 + a2 b2 - b2^2 - a1 c1 - b1 c1 + c1^2 + a2 c2 + b2 c2 - c2^2",
+    );
+    let f2 = parse("2 a1 a2 - a2 b1 - a1 b2 + 2 b1 b2 - a2 c1 - b2 c1 - a1 c2 - b1 c2 + 2 c1 c2");
+    let g1 = parse("a1^2 + a2^2 - 2 a1 b1 - 2 a2 b2 + 2 b1 c1 - c1^2 + 2 b2 c2 - c2^2");
+    let g2 = parse("-a1^2 - a2^2 + b1^2 + b2^2 + 2 a1 c1 - 2 b1 c1 + 2 a2 c2 - 2 b2 c2");
+    let one = parse("1");
+    let y = parse("y");
+
+    vec![
+        vec![f1.clone(), f2.clone(), f3.clone(), &one - &y * &g1],
+        vec![f1.clone(), f2.clone(), f3.clone(), &one - &y * &g2],
+        vec![g1.clone(), g2.clone(), f3.clone(), &one - &y * &f1],
+        vec![g1, g2, f3, &one - &y * &f2],
+    ]
+}
+
+// tokyo-tech-2020-2-2: 外接円上の点までの距離の和 (g) と 4 乗和 (h) の系
+fn tokyo_tech_2020_2_2() -> Vec<Vec<Polynomial>> {
+    let ring = PolynomialRing::new(
+        &["b1", "c1", "c2", "m1", "m2", "p1", "p2", "v", "r", "d"],
+        MonomialOrder::Lex,
+    );
+    let parse = parser(&ring);
+
+    let fs = vec![
+        parse("c1^2 + c2^2 - b1^2"),
+        parse("(c1 - b1)^2 + c2^2 - b1^2"),
+        parse("m1^2 + m2^2 - r^2"),
+        parse("(b1 - m1)^2 + (0 - m2)^2 - r^2"),
+        parse("(c1 - m1)^2 + (c2 - m2)^2 - r^2"),
+        parse("(p1 - m1)^2 + (p2 - m2)^2 - r^2"),
+        parse("b1 * v - 1"),
+    ];
+    let g = parse("p1^2 + p2^2 + (p1 - b1)^2 + p2^2 + (p1 - c1)^2 + (p2 - c2)^2 - d");
+    let h = parse("(p1^2 + p2^2)^2 + ((p1 - b1)^2 + p2^2)^2 + ((p1 - c1)^2 + (p2 - c2)^2)^2 - d");
+
+    let mut fg = fs.clone();
+    fg.push(g);
+    let mut fh = fs;
+    fh.push(h);
+    vec![fg, fh]
+}
+
+fn bench<F: FnMut()>(name: &str, runs: usize, mut f: F) {
+    let mut times = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect::<Vec<Duration>>();
+    times.sort();
+    println!(
+        "{:<24} runs {:>3}  min {:>10.3?}  median {:>10.3?}",
+        name,
+        runs,
+        times[0],
+        times[runs / 2]
+    );
+}
+
+fn main() {
+    // cargo bench は --bench を渡してくるので数だけを拾う
+    let runs = std::env::args()
+        .skip(1)
+        .find_map(|a| a.parse::<usize>().ok())
+        .unwrap_or(5)
+        .max(1);
+
+    let systems = tokyo_tech_2020_2_1();
+    bench("tokyo-tech-2020-2-1", runs, || {
+        for fs in &systems {
+            compute_groebner_basis(fs.clone());
+        }
+    });
+
+    let systems = tokyo_tech_2020_2_2();
+    bench("tokyo-tech-2020-2-2", runs, || {
+        for fs in &systems {
+            compute_groebner_basis(fs.clone());
+            eliminate(fs.clone(), &[0, 1, 2, 3, 4, 5, 6, 7]);
+        }
+    });
+}
@@ -1,8 +1,7 @@
 extern crate groebner_basis;
 
-use groebner_basis::monomial::{Monomial, MonomialOrder};
-use groebner_basis::polynomial::{Polynomial, PolynomialHandlers};
-use groebner_basis::scalar::Rational;
+use groebner_basis::monomial::MonomialOrder;
+use groebner_basis::polynomial::{Polynomial, PolynomialRing};
 
 /*
  *
@@ -64,180 +63,23 @@ GroebnerBasis[{g1, g2, f3, 1 - y * f2}, {a1, a2, b1, b2, c1, c2, v, y}]
 *
 */
 fn main() {
-    // a1 -> x1
-    // a2 -> x2
-
-    // b1 -> x3
-    // b2 -> x4
-
-    // c1 -> x5
-    // c2 -> x6
-
-    // v -> x7
-
-    // y -> x8
-
-    // f3 = -1 + a2 b1 v - a1 b2 v - a2 c1 v + b2 c1 v + a1 c2 v - b1 c2 v
-
-    let monomial_order = MonomialOrder::Grlex;
-
-    let mut f3 = Polynomial::from((8, monomial_order));
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 0, 0, 0];
-    f3.add_term(Rational::from(-1), Monomial::from(degree)); // -1
-
-    let degree: Vec<i64> = vec![0, 1, 1, 0, 0, 0, 1, 0];
-    f3.add_term(Rational::from(1), Monomial::from(degree)); // a2 b1 v
-
-    let degree: Vec<i64> = vec![1, 0, 0, 1, 0, 0, 1, 0];
-    f3.add_term(Rational::from(-1), Monomial::from(degree)); // -a1 b2 v
-
-    let degree: Vec<i64> = vec![0, 1, 0, 0, 1, 0, 1, 0];
-    f3.add_term(Rational::from(-1), Monomial::from(degree)); // -a2 c1 v
-
-    let degree: Vec<i64> = vec![0, 0, 0, 1, 1, 0, 1, 0];
-    f3.add_term(Rational::from(1), Monomial::from(degree)); // b2 c1 v
-
-    let degree: Vec<i64> = vec![1, 0, 0, 0, 0, 1, 1, 0];
-    f3.add_term(Rational::from(1), Monomial::from(degree)); // a1 c2 v
-
-    let degree: Vec<i64> = vec![0, 0, 1, 0, 0, 1, 1, 0];
-    f3.add_term(Rational::from(-1), Monomial::from(degree)); // -b1 c2 v
-
-    // f1 = a1^2 - a2^2 - a1 b1 + b1^2 + a2 b2 - b2^2 - a1 c1 - b1 c1 + c1^2 + a2 c2 + b2 c2 - c2^2
-    let mut f1 = Polynomial::from((8, monomial_order));
-
-    let degree: Vec<i64> = vec![2, 0, 0, 0, 0, 0, 0, 0];
-    f1.add_term(Rational::from(1), Monomial::from(degree)); // a1^2
-
-    let degree: Vec<i64> = vec![0, 2, 0, 0, 0, 0, 0, 0];
-    f1.add_term(Rational::from(-1), Monomial::from(degree)); // -a2^2
-
-    let degree: Vec<i64> = vec![1, 0, 1, 0, 0, 0, 0, 0];
-    f1.add_term(Rational::from(-1), Monomial::from(degree)); // -a1 b1
-
-    let degree: Vec<i64> = vec![0, 0, 2, 0, 0, 0, 0, 0];
-    f1.add_term(Rational::from(1), Monomial::from(degree)); // b1^2
-
-    let degree: Vec<i64> = vec![0, 1, 0, 1, 0, 0, 0, 0];
-    f1.add_term(Rational::from(1), Monomial::from(degree)); // a2 b2
-
-    let degree: Vec<i64> = vec![0, 0, 0, 2, 0, 0, 0, 0];
-    f1.add_term(Rational::from(-1), Monomial::from(degree)); // -b2^2
-
-    let degree: Vec<i64> = vec![1, 0, 0, 0, 1, 0, 0, 0];
-    f1.add_term(Rational::from(-1), Monomial::from(degree)); // -a1 c1
-
-    let degree: Vec<i64> = vec![0, 0, 1, 0, 1, 0, 0, 0];
-    f1.add_term(Rational::from(-1), Monomial::from(degree)); // -b1 c1
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 2, 0, 0, 0];
-    f1.add_term(Rational::from(1), Monomial::from(degree)); // c1^2
-
-    let degree: Vec<i64> = vec![0, 1, 0, 0, 0, 1, 0, 0];
-    f1.add_term(Rational::from(1), Monomial::from(degree)); // a2 c2
-
-    let degree: Vec<i64> = vec![0, 0, 0, 1, 0, 1, 0, 0];
-    f1.add_term(Rational::from(1), Monomial::from(degree)); // b2 c2
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 2, 0, 0];
-    f1.add_term(Rational::from(-1), Monomial::from(degree)); // -c2^2
-
-    // f2 = 2 a1 a2 - a2 b1 - a1 b2 + 2 b1 b2 - a2 c1 - b2 c1 - a1 c2 - b1 c2 + 2 c1 c2
-    let mut f2 = Polynomial::from((8, monomial_order));
-
-    let degree: Vec<i64> = vec![1, 1, 0, 0, 0, 0, 0, 0];
-    f2.add_term(Rational::from(2), Monomial::from(degree)); // 2 a1 a2
-
-    let degree: Vec<i64> = vec![0, 1, 1, 0, 0, 0, 0, 0];
-    f2.add_term(Rational::from(-1), Monomial::from(degree)); // - a2 b1
-
-    let degree: Vec<i64> = vec![1, 0, 0, 1, 0, 0, 0, 0];
-    f2.add_term(Rational::from(-1), Monomial::from(degree)); // - a1 b2
-
-    let degree: Vec<i64> = vec![0, 0, 1, 1, 0, 0, 0, 0];
-    f2.add_term(Rational::from(2), Monomial::from(degree)); // 2 b1 b2
-
-    let degree: Vec<i64> = vec![0, 1, 0, 0, 1, 0, 0, 0];
-    f2.add_term(Rational::from(-1), Monomial::from(degree)); // - a2 c1
-
-    let degree: Vec<i64> = vec![0, 0, 0, 1, 1, 0, 0, 0];
-    f2.add_term(Rational::from(-1), Monomial::from(degree)); // - b2 c1
-
-    let degree: Vec<i64> = vec![1, 0, 0, 0, 0, 1, 0, 0];
-    f2.add_term(Rational::from(-1), Monomial::from(degree)); // - a1 c2
-
-    let degree: Vec<i64> = vec![0, 0, 1, 0, 0, 1, 0, 0];
-    f2.add_term(Rational::from(-1), Monomial::from(degree)); // - b1 c2
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 1, 1, 0, 0];
-    f2.add_term(Rational::from(2), Monomial::from(degree)); // 2 c1 c2
-
-    // g1 = a1^2 + a2^2 - 2 a1 b1 - 2 a2 b2 + 2 b1 c1 - c1^2 + 2 b2 c2 - c2^2
-    let mut g1 = Polynomial::from((8, monomial_order));
-
-    let degree: Vec<i64> = vec![2, 0, 0, 0, 0, 0, 0, 0];
-    g1.add_term(Rational::from(1), Monomial::from(degree)); // a1^2
-
-    let degree: Vec<i64> = vec![0, 2, 0, 0, 0, 0, 0, 0];
-    g1.add_term(Rational::from(1), Monomial::from(degree)); // a2^2
-
-    let degree: Vec<i64> = vec![1, 0, 1, 0, 0, 0, 0, 0];
-    g1.add_term(Rational::from(-2), Monomial::from(degree)); // -2 a1 b1
-
-    let degree: Vec<i64> = vec![0, 1, 0, 1, 0, 0, 0, 0];
-    g1.add_term(Rational::from(-2), Monomial::from(degree)); // -2 a2 b2
-
-    let degree: Vec<i64> = vec![0, 0, 1, 0, 1, 0, 0, 0];
-    g1.add_term(Rational::from(2), Monomial::from(degree)); // 2 b1 c1
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 2, 0, 0, 0];
-    g1.add_term(Rational::from(-1), Monomial::from(degree)); // -c1^2
-
-    let degree: Vec<i64> = vec![0, 0, 0, 1, 0, 1, 0, 0];
-    g1.add_term(Rational::from(2), Monomial::from(degree)); // 2 b2 c2
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 2, 0, 0];
-    g1.add_term(Rational::from(-1), Monomial::from(degree)); // -c2^2
-
-    // g2 = -a1^2 - a2^2 + b1^2 + b2^2 + 2 a1 c1 - 2 b1 c1 + 2 a2 c2 - 2 b2 c2
-    let mut g2 = Polynomial::from((8, monomial_order));
-
-    let degree: Vec<i64> = vec![2, 0, 0, 0, 0, 0, 0, 0];
-    g2.add_term(Rational::from(-1), Monomial::from(degree)); // -a1^2
-
-    let degree: Vec<i64> = vec![0, 2, 0, 0, 0, 0, 0, 0];
-    g2.add_term(Rational::from(-1), Monomial::from(degree)); // -a2^2
-
-    let degree: Vec<i64> = vec![0, 0, 2, 0, 0, 0, 0, 0];
-    g2.add_term(Rational::from(1), Monomial::from(degree)); // b1^2
-
-    let degree: Vec<i64> = vec![0, 0, 0, 2, 0, 0, 0, 0];
-    g2.add_term(Rational::from(1), Monomial::from(degree)); // b2^2
-
-    let degree: Vec<i64> = vec![1, 0, 0, 0, 1, 0, 0, 0];
-    g2.add_term(Rational::from(2), Monomial::from(degree)); // 2 a1 c1
-
-    let degree: Vec<i64> = vec![0, 0, 1, 0, 1, 0, 0, 0];
-    g2.add_term(Rational::from(-2), Monomial::from(degree)); // -2 b1 c1
-
-    let degree: Vec<i64> = vec![0, 1, 0, 0, 0, 1, 0, 0];
-    g2.add_term(Rational::from(2), Monomial::from(degree)); // 2 a2 c2
-
-    let degree: Vec<i64> = vec![0, 0, 0, 1, 0, 1, 0, 0];
-    g2.add_term(Rational::from(-2), Monomial::from(degree)); // -2 b2 c2
-
-    // 1
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 0, 0, 0];
-    let one = Polynomial::from((Monomial::from(degree), monomial_order));
-
-    // y
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 0, 0, 1];
-    let y = Polynomial::from((Monomial::from(degree), monomial_order));
-
-    // h1 = 1 - y * g1
+    let ring = PolynomialRing::new(
+        &["a1", "a2", "b1", "b2", "c1", "c2", "v", "y"],
+        MonomialOrder::Grlex,
+    );
+    let parse = |s: &str| -> Polynomial { ring.parse(s).unwrap_or_else(|e| panic!("{}", e)) };
+
+    let f3 = parse("-1 + a2 b1 v - a1 b2 v - a2 c1 v + b2 c1 v + a1 c2 v - b1 c2 v");
+    let f1 = parse(
+        "a1^2 - a2^2 - a1 b1 + b1^2 + a2 b2 - b2^2 - a1 c1 - b1 c1 + c1^2 + a2 c2 + b2 c2 - c2^2",
+    );
+    let f2 = parse("2 a1 a2 - a2 b1 - a1 b2 + 2 b1 b2 - a2 c1 - b2 c1 - a1 c2 - b1 c2 + 2 c1 c2");
+    let g1 = parse("a1^2 + a2^2 - 2 a1 b1 - 2 a2 b2 + 2 b1 c1 - c1^2 + 2 b2 c2 - c2^2");
+    let g2 = parse("-a1^2 - a2^2 + b1^2 + b2^2 + 2 a1 c1 - 2 b1 c1 + 2 a2 c2 - 2 b2 c2");
+
+    let one = parse("1");
+    let y = parse("y");
     let h1 = &one - &y * &g1;
-    // h2 = 1 - y * g2
     let h2 = &one - &y * &g2;
 
     let fs = vec![f1.clone(), f2.clone(), f3.clone(), h1];
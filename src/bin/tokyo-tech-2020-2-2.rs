@@ -1,7 +1,7 @@
 extern crate groebner_basis;
 
-use groebner_basis::monomial::{Monomial, MonomialOrder};
-use groebner_basis::polynomial::{Polynomial, PolynomialHandlers};
+use groebner_basis::monomial::MonomialOrder;
+use groebner_basis::polynomial::{Polynomial, PolynomialRing};
 
 /*
 (* A(0, 0) *) (* Aは原点に固定する(一般性は失わない)*)
@@ -49,83 +49,22 @@ GroebnerBasis[{f1, f2, f3, f4, f5, f6, f7, g}, {b1, c1, c2, m1, m2, p1, p2, v, r
 GroebnerBasis[{f1, f2, f3, f4, f5, f6, f7, h}, {b1, c1, c2, m1, m2, p1, p2, v, r, d2}]
 */
 
-fn sq(v: Polynomial) -> Polynomial {
-    &v * &v
-}
-
 fn main() {
-    // b1 -> x1
-
-    // c1 -> x2
-    // c2 -> x3
-
-    // m1 -> x4
-    // m2 -> x5
-
-    // p1 -> x6
-    // p2 -> x7
-
-    // v  -> x8
-
-    // r  -> x9
-
-    // d  -> x10
-
-    let monomial_order = MonomialOrder::Lex;
-
-    let degree: Vec<i64> = vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-    let b1 = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0];
-    let c1 = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
-    let c2 = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
-    let m1 = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 1, 0, 0, 0, 0, 0];
-    let m2 = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0];
-    let p1 = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 0, 1, 0, 0, 0];
-    let p2 = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0];
-    let v = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 0, 0, 0, 1, 0];
-    let r = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
-    let d = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let degree: Vec<i64> = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-    let one = Polynomial::from(Monomial::from((degree, monomial_order)));
-
-    let f1 = &c1 * &c1 + &c2 * &c2 - &b1 * &b1;
-
-    let f2 = sq(&c1 - &b1) + &c2 * &c2 - &b1 * &b1;
-
-    let f3 = &m1 * &m1 + &m2 * &m2 - &r * &r;
-
-    let f4 = sq(&b1 - &m1) + &m2 * &m2 - &r * &r;
-
-    let f5 = sq(&c1 - &m1) + sq(&c2 - &m2) - &r * &r;
-
-    let f6 = sq(&p1 - &m1) + sq(&p2 - &m2) - &r * &r;
-
-    let f7 = &b1 * &v - &one;
-
-    let g = &p1 * &p1 + &p2 * &p2 + sq(&p1 - &b1) + &p2 * &p2 + sq(&p1 - &c1) + sq(&p2 - &c2) - &d;
-
-    let h = sq(&p1 * &p1 + &p2 * &p2)
-        + sq(sq(&p1 - &b1) + &p2 * &p2)
-        + sq(sq(&p1 - &c1) + sq(&p2 - &c2))
-        - &d;
+    let ring = PolynomialRing::new(
+        &["b1", "c1", "c2", "m1", "m2", "p1", "p2", "v", "r", "d"],
+        MonomialOrder::Lex,
+    );
+    let parse = |s: &str| -> Polynomial { ring.parse(s).unwrap_or_else(|e| panic!("{}", e)) };
+
+    let f1 = parse("c1^2 + c2^2 - b1^2");
+    let f2 = parse("(c1 - b1)^2 + c2^2 - b1^2");
+    let f3 = parse("m1^2 + m2^2 - r^2");
+    let f4 = parse("(b1 - m1)^2 + (0 - m2)^2 - r^2");
+    let f5 = parse("(c1 - m1)^2 + (c2 - m2)^2 - r^2");
+    let f6 = parse("(p1 - m1)^2 + (p2 - m2)^2 - r^2");
+    let f7 = parse("b1 * v - 1");
+    let g = parse("p1^2 + p2^2 + (p1 - b1)^2 + p2^2 + (p1 - c1)^2 + (p2 - c2)^2 - d");
+    let h = parse("(p1^2 + p2^2)^2 + ((p1 - b1)^2 + p2^2)^2 + ((p1 - c1)^2 + (p2 - c2)^2)^2 - d");
 
     let fs = vec![
         f1.clone(),
@@ -138,7 +77,7 @@ fn main() {
         g.clone(),
     ];
 
-    let ps = groebner_basis::groebner_basis::compute_groebner_basis(fs);
+    let ps = groebner_basis::groebner_basis::compute_groebner_basis(fs.clone());
 
     println!("GroebnerBasis[f1, f2, f3, f4, f5, f6, f7, g]");
     for (i, p) in ps.iter().enumerate() {
@@ -146,6 +85,15 @@ fn main() {
     }
     println!("\n\n");
 
+    // b1, ..., v を消去する
+    let es = groebner_basis::groebner_basis::eliminate(fs, &[0, 1, 2, 3, 4, 5, 6, 7]);
+
+    println!("Eliminate[f1, f2, f3, f4, f5, f6, f7, g]");
+    for (i, e) in es.iter().enumerate() {
+        println!("  |  e_{} = {}", i + 1, e.clone().integer_coeff());
+    }
+    println!("\n\n");
+
     let fs = vec![
         f1.clone(),
         f2.clone(),
@@ -157,11 +105,20 @@ fn main() {
         h.clone(),
     ];
 
-    let qs = groebner_basis::groebner_basis::compute_groebner_basis(fs);
+    let qs = groebner_basis::groebner_basis::compute_groebner_basis(fs.clone());
 
     println!("GroebnerBasis[f1, f2, f3, f4, f5, f6, f7, h]");
     for (i, q) in qs.iter().enumerate() {
         println!("  |  q_{} = {}", i + 1, q.clone().integer_coeff());
     }
     println!("\n\n");
+
+    // b1, ..., v を消去する
+    let es = groebner_basis::groebner_basis::eliminate(fs, &[0, 1, 2, 3, 4, 5, 6, 7]);
+
+    println!("Eliminate[f1, f2, f3, f4, f5, f6, f7, h]");
+    for (i, e) in es.iter().enumerate() {
+        println!("  |  e_{} = {}", i + 1, e.clone().integer_coeff());
+    }
+    println!("\n\n");
 }
@@ -1,4 +1,7 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt::{Debug, Display, Error, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub};
 
 use crate::scalar::Integer;
@@ -10,10 +13,7 @@ pub struct Zn {
 
 impl From<Vec<i64>> for Zn {
     fn from(v: Vec<i64>) -> Self {
-        let v = v
-            .into_iter()
-            .map(|i| Integer::from(i))
-            .collect::<Vec<Integer>>();
+        let v = v.into_iter().map(Integer::from).collect::<Vec<Integer>>();
         Zn::from(v)
     }
 }
@@ -24,9 +24,9 @@ impl From<Vec<Integer>> for Zn {
     }
 }
 
-impl Into<Vec<Integer>> for Zn {
-    fn into(self) -> Vec<Integer> {
-        self.v
+impl From<Zn> for Vec<Integer> {
+    fn from(z: Zn) -> Self {
+        z.v
     }
 }
 
@@ -39,7 +39,7 @@ impl Display for Zn {
             if i < self.v.len() - 1 {
                 comma_separated.push_str(", ");
             } else {
-                comma_separated.push_str(" ");
+                comma_separated.push(' ');
             }
         }
 
@@ -53,26 +53,22 @@ impl Add<Zn> for Zn {
     fn add(self, other: Zn) -> Zn {
         let mut ret = self.v.clone();
 
-        let n = std::cmp::min(ret.len(), other.v.len());
-
-        for i in 0..n {
-            ret[i] = &ret[i] + &other.v[i];
+        for (r, o) in ret.iter_mut().zip(other.v.iter()) {
+            *r = &*r + o;
         }
 
         Zn { v: ret }
     }
 }
 
-impl<'a, 'b> Add<&'a Zn> for &'b Zn {
+impl Add<&Zn> for &Zn {
     type Output = Zn;
 
     fn add(self, other: &Zn) -> Zn {
         let mut ret = self.v.clone();
 
-        let n = std::cmp::min(ret.len(), other.v.len());
-
-        for i in 0..n {
-            ret[i] = &ret[i] + &other.v[i];
+        for (r, o) in ret.iter_mut().zip(other.v.iter()) {
+            *r = &*r + o;
         }
 
         Zn { v: ret }
@@ -85,26 +81,22 @@ impl Sub for Zn {
     fn sub(self, other: Zn) -> Zn {
         let mut ret = self.v.clone();
 
-        let n = std::cmp::min(ret.len(), other.v.len());
-
-        for i in 0..n {
-            ret[i] = &ret[i] - &other.v[i];
+        for (r, o) in ret.iter_mut().zip(other.v.iter()) {
+            *r = &*r - o;
         }
 
         Zn { v: ret }
     }
 }
 
-impl<'a, 'b> Sub<&'a Zn> for &'b Zn {
+impl Sub<&Zn> for &Zn {
     type Output = Zn;
 
     fn sub(self, other: &Zn) -> Zn {
         let mut ret = self.v.clone();
 
-        let n = std::cmp::min(ret.len(), other.v.len());
-
-        for i in 0..n {
-            ret[i] = &ret[i] - &other.v[i];
+        for (r, o) in ret.iter_mut().zip(other.v.iter()) {
+            *r = &*r - o;
         }
 
         Zn { v: ret }
@@ -117,28 +109,286 @@ impl PartialEq for Zn {
         if self.v.len() != other.v.len() {
             return false;
         }
-        let n = self.v.len();
-
-        for i in 0..n {
-            if self.v[i] != other.v[i] {
-                return false;
-            }
-        }
-        true
+        self.v.iter().zip(other.v.iter()).all(|(l, r)| l == r)
     }
 }
 impl Eq for Zn {}
 
 pub trait Degree {
-    fn get_ref_v<'a>(self: &'a Self) -> &'a Vec<Integer>;
+    fn get_ref_v(&self) -> &Vec<Integer>;
     fn dim(&self) -> usize;
 }
 
 impl Degree for Zn {
-    fn get_ref_v<'a>(self: &'a Self) -> &'a Vec<Integer> {
+    fn get_ref_v(&self) -> &Vec<Integer> {
         &(self.v)
     }
     fn dim(&self) -> usize {
-        return self.v.len();
+        self.v.len()
+    }
+}
+
+// これ以下の変数の数ならヒープを使わない
+const INLINE_CAPACITY: usize = 10;
+
+#[derive(Clone)]
+enum Storage {
+    Inline { len: u8, v: [u32; INLINE_CAPACITY] },
+    Heap(Vec<u32>),
+}
+
+/// u32 の指数を並べた指数ベクトル
+///
+/// 変数の数が少なければ配列に直接持つ。和が u32 に収まらない時、差が負になる時は panic する。
+#[derive(Clone)]
+pub struct ExponentVector {
+    storage: Storage,
+}
+
+impl ExponentVector {
+    pub fn from_slice(v: &[u32]) -> Self {
+        let storage = if v.len() <= INLINE_CAPACITY {
+            let mut a = [0; INLINE_CAPACITY];
+            a[..v.len()].copy_from_slice(v);
+            Storage::Inline {
+                len: v.len() as u8,
+                v: a,
+            }
+        } else {
+            Storage::Heap(v.to_vec())
+        };
+        Self { storage }
+    }
+
+    /// 0ベクトル
+    pub fn zeros(n: usize) -> Self {
+        Self::from_slice(&vec![0; n])
+    }
+
+    pub fn as_slice(&self) -> &[u32] {
+        match &self.storage {
+            Storage::Inline { len, v } => &v[..*len as usize],
+            Storage::Heap(v) => v,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    pub fn total_degree(&self) -> u64 {
+        self.as_slice().iter().map(|&e| e as u64).sum()
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u32, u32) -> Option<u32>) -> Option<Self> {
+        assert_eq!(self.len(), other.len());
+
+        let (l, r) = (self.as_slice(), other.as_slice());
+        let mut ret = self.clone();
+        let v = match &mut ret.storage {
+            Storage::Inline { len, v } => &mut v[..*len as usize],
+            Storage::Heap(v) => &mut v[..],
+        };
+        for ((x, &a), &b) in v.iter_mut().zip(l.iter()).zip(r.iter()) {
+            *x = f(a, b)?;
+        }
+        Some(ret)
+    }
+
+    /// 和 (溢れたら None)
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.zip_with(other, u32::checked_add)
+    }
+
+    /// 差 (負になる成分があれば None)
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.zip_with(other, u32::checked_sub)
+    }
+
+    /// 成分ごとの最大値
+    pub fn max(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| Some(std::cmp::max(a, b)))
+            .unwrap()
+    }
+
+    /// 全ての成分が other 以上か
+    pub fn is_divisible_by(&self, other: &Self) -> bool {
+        assert_eq!(self.len(), other.len());
+        self.as_slice()
+            .iter()
+            .zip(other.as_slice().iter())
+            .all(|(a, b)| a >= b)
+    }
+}
+
+impl TryFrom<&[Integer]> for ExponentVector {
+    type Error = String;
+
+    fn try_from(v: &[Integer]) -> Result<Self, Self::Error> {
+        let v = v
+            .iter()
+            .map(|a| {
+                let a: Option<u64> = a.into();
+                a.and_then(|a| u32::try_from(a).ok())
+                    .ok_or_else(|| format!("exponent out of range: {:?}", v))
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+        Ok(Self::from_slice(&v))
+    }
+}
+
+impl From<&ExponentVector> for Vec<Integer> {
+    fn from(v: &ExponentVector) -> Self {
+        v.as_slice()
+            .iter()
+            .map(|&e| Integer::from(e as u64))
+            .collect()
     }
 }
+
+impl Add<&ExponentVector> for &ExponentVector {
+    type Output = ExponentVector;
+
+    fn add(self, other: &ExponentVector) -> ExponentVector {
+        self.checked_add(other)
+            .unwrap_or_else(|| panic!("exponent overflow: {:?} + {:?}", self, other))
+    }
+}
+
+impl Sub<&ExponentVector> for &ExponentVector {
+    type Output = ExponentVector;
+
+    fn sub(self, other: &ExponentVector) -> ExponentVector {
+        self.checked_sub(other)
+            .unwrap_or_else(|| panic!("negative exponent: {:?} - {:?}", self, other))
+    }
+}
+
+impl PartialEq for ExponentVector {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for ExponentVector {}
+
+impl Hash for ExponentVector {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl Debug for ExponentVector {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{:?}", self.as_slice())
+    }
+}
+
+// 1ワードに詰める指数の数 (各16ビットで最上位はガードビット)
+const PACKED_FIELDS_PER_WORD: usize = 4;
+const PACKED_FIELD_BITS: usize = 16;
+const PACKED_WORDS: usize = 4;
+const PACKED_GUARD: u64 = 0x8000_8000_8000_8000;
+
+/// 指数ベクトルを u64 に詰めたもの
+///
+/// 変数が16個以下で全ての指数が 2^15 未満なら、4個ずつ先頭の変数が上位に来るように詰める。
+/// 詰められたもの同士は割り切れるかの判定と辞書式順序の比較がワード単位でできる。
+/// 全次数と、割り切れるなら必ず包含関係になるビットマスクは常に持つ。
+#[derive(Clone, Copy, Debug)]
+pub struct PackedExponents {
+    words: [u64; PACKED_WORDS],
+    packed: bool,
+    total_degree: u64,
+    mask: u64,
+}
+
+impl PackedExponents {
+    pub fn new(v: &[u32]) -> Self {
+        let n = v.len();
+        let mut words = [0; PACKED_WORDS];
+        let mut packed = n <= PACKED_WORDS * PACKED_FIELDS_PER_WORD;
+        let mut total_degree = 0;
+        let mut mask = 0;
+
+        // 変数 i に bits 個のビットを割り当て、j 番目のビットは指数が j より大きい時に立てる
+        let bits = 64usize.checked_div(n).map_or(0, |b| std::cmp::max(b, 1));
+        for (i, &e) in v.iter().enumerate() {
+            let e = e as u64;
+            total_degree += e;
+
+            if n <= 64 {
+                let k = std::cmp::min(e, bits as u64) as usize;
+                mask |= (((1u128 << k) - 1) << (i * bits)) as u64;
+            } else if e > 0 {
+                mask |= 1 << (i % 64);
+            }
+
+            if packed && e < 1 << (PACKED_FIELD_BITS - 1) {
+                let shift =
+                    (PACKED_FIELDS_PER_WORD - 1 - i % PACKED_FIELDS_PER_WORD) * PACKED_FIELD_BITS;
+                words[i / PACKED_FIELDS_PER_WORD] |= e << shift;
+            } else {
+                packed = false;
+            }
+        }
+
+        Self {
+            words,
+            packed,
+            total_degree,
+            mask,
+        }
+    }
+
+    pub fn total_degree(&self) -> u64 {
+        self.total_degree
+    }
+
+    /// 割り切れるかの判定に使うビットマスク
+    pub fn mask(&self) -> u64 {
+        self.mask
+    }
+
+    /// ワード単位で比較できるか
+    pub fn is_packed(&self) -> bool {
+        self.packed
+    }
+
+    /// false なら割り切れない (true でも割り切れるとは限らない)
+    pub fn may_be_divisible_by(&self, other: &Self) -> bool {
+        self.total_degree >= other.total_degree && other.mask & !self.mask == 0
+    }
+
+    /// 割り切れるか (詰められていなければ None)
+    pub fn is_divisible_by(&self, other: &Self) -> Option<bool> {
+        if !self.may_be_divisible_by(other) {
+            return Some(false);
+        }
+        if !(self.packed && other.packed) {
+            return None;
+        }
+        // 各フィールドで a >= b ならガードビットが残る
+        Some(
+            self.words
+                .iter()
+                .zip(other.words.iter())
+                .all(|(a, b)| ((a | PACKED_GUARD) - b) & PACKED_GUARD == PACKED_GUARD),
+        )
+    }
+
+    /// 辞書式順序での比較 (詰められていなければ None)
+    pub fn cmp_lex(&self, other: &Self) -> Option<Ordering> {
+        if self.packed && other.packed {
+            Some(self.words.cmp(&other.words))
+        } else {
+            None
+        }
+    }
+}
+
+mod test;
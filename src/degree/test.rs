@@ -0,0 +1,78 @@
+#[cfg(test)]
+#[allow(unused_imports)]
+use super::{ExponentVector, PackedExponents};
+#[allow(unused_imports)]
+use crate::monomial::{Monomial, MonomialOrder};
+#[allow(unused_imports)]
+use crate::scalar::Integer;
+#[allow(unused_imports)]
+use std::convert::TryFrom;
+
+#[test]
+fn test_exponent_vector() {
+    // 変数が少なければインライン、多ければヒープ
+    for n in [3, 10, 11, 20] {
+        let a: ExponentVector = ExponentVector::from_slice(&(0..n).collect::<Vec<u32>>());
+        let b: ExponentVector = ExponentVector::from_slice(&vec![1; n as usize]);
+        let c = &a + &b;
+        assert_eq!(c.as_slice(), &(1..=n).collect::<Vec<u32>>()[..]);
+        assert_eq!(&c - &b, a);
+        assert!(c.is_divisible_by(&a));
+        assert_eq!(a.checked_sub(&b), None);
+        assert_eq!(c.total_degree(), (n as u64) * (n as u64 + 1) / 2);
+        assert_eq!(a.max(&b).len(), n as usize);
+    }
+
+    // オーバーフローは検出する
+    let a = ExponentVector::from_slice(&[u32::MAX, 0]);
+    let b = ExponentVector::from_slice(&[1, 0]);
+    assert_eq!(a.checked_add(&b), None);
+    assert!(ExponentVector::try_from(&[Integer::from(1i64 << 32)][..]).is_err());
+    assert!(ExponentVector::try_from(&[Integer::from(-1)][..]).is_err());
+}
+
+#[test]
+#[should_panic(expected = "exponent overflow")]
+fn test_monomial_exponent_overflow() {
+    let m = Monomial::from((vec![u32::MAX as i64, 0], MonomialOrder::Lex));
+    let x = Monomial::from((vec![1, 0], MonomialOrder::Lex));
+    let _ = &m * &x;
+}
+
+#[test]
+fn test_packed_exponents() {
+    let mut seed = 88172645463325252u64;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+
+    // 詰められる場合と詰められない場合 (変数が多い、指数が大きい) で素朴な判定と一致する
+    for &(n, max) in &[(3, 4), (8, 3), (16, 2), (17, 2), (70, 1), (4, 1 << 16)] {
+        for _ in 0..500 {
+            let a = (0..n)
+                .map(|_| (next() % (max + 1)) as u32)
+                .collect::<Vec<_>>();
+            let b = (0..n)
+                .map(|_| (next() % (max + 1)) as u32)
+                .collect::<Vec<_>>();
+            let (pa, pb) = (PackedExponents::new(&a), PackedExponents::new(&b));
+            assert_eq!(pa.is_packed(), n <= 16 && a.iter().all(|&e| e < 1 << 15));
+
+            let divisible = a.iter().zip(b.iter()).all(|(x, y)| x >= y);
+            assert_eq!(
+                pa.is_divisible_by(&pb).unwrap_or(divisible),
+                divisible,
+                "{:?} {:?}",
+                a,
+                b
+            );
+            if divisible {
+                assert!(pa.may_be_divisible_by(&pb));
+            }
+            assert_eq!(pa.cmp_lex(&pb).unwrap_or_else(|| a.cmp(&b)), a.cmp(&b));
+        }
+    }
+}
@@ -1,13 +1,63 @@
 use std::cmp::Ordering;
 
 use crate::polynomial::{Polynomial, PolynomialHandlers};
-use crate::scalar::Integer;
+use crate::scalar::{Field, Integer, Ring};
 use crate::{monomial, polynomial};
-use monomial::{Monomial, MonomialHandlers};
+use monomial::{Monomial, MonomialHandlers, MonomialOrdering};
 
 use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+mod comprehensive;
+mod elimination;
+mod modular;
+pub use comprehensive::{comprehensive_groebner_system, ParametricCell};
+pub use elimination::eliminate;
+pub use modular::{compute_groebner_basis_modular, ModularStatus};
+
+// Buchberger算法の中で係数の種類によって変わる操作
+trait Reduction<K: Ring> {
+    fn s_polynomial(f: &Polynomial<K>, g: &Polynomial<K>) -> Option<Polynomial<K>>;
+    fn reduce(f: &Polynomial<K>, gs: &[&Polynomial<K>]) -> Polynomial<K>;
+    fn normalize(f: Polynomial<K>) -> Polynomial<K>;
+}
+
+// 体上: 割り算の余りを取り、モニックにする
+struct FieldReduction<K: Field>(PhantomData<K>);
+
+impl<K: Field> Reduction<K> for FieldReduction<K> {
+    fn s_polynomial(f: &Polynomial<K>, g: &Polynomial<K>) -> Option<Polynomial<K>> {
+        polynomial::s_polynomial(f, g)
+    }
+    fn reduce(f: &Polynomial<K>, gs: &[&Polynomial<K>]) -> Polynomial<K> {
+        f.reduce(gs)
+    }
+    fn normalize(f: Polynomial<K>) -> Polynomial<K> {
+        f.normalize()
+    }
+}
+
+// 整数係数: 擬簡約し、原始的にする
+struct FractionFreeReduction;
+
+impl Reduction<Integer> for FractionFreeReduction {
+    fn s_polynomial(
+        f: &Polynomial<Integer>,
+        g: &Polynomial<Integer>,
+    ) -> Option<Polynomial<Integer>> {
+        polynomial::s_polynomial_fraction_free(f, g)
+    }
+    fn reduce(f: &Polynomial<Integer>, gs: &[&Polynomial<Integer>]) -> Polynomial<Integer> {
+        f.pseudo_reduce(gs)
+    }
+    fn normalize(f: Polynomial<Integer>) -> Polynomial<Integer> {
+        f.primitive_part()
+    }
+}
 
-fn to_minimal_groebner_basis(v: Vec<Polynomial>) -> Vec<Polynomial> {
+fn to_minimal_groebner_basis<K: Ring, R: Reduction<K>>(
+    v: Vec<Polynomial<K>>,
+) -> Vec<Polynomial<K>> {
     // TODO Linked List がまともになったらLinked Listを使う
     let mut leaves = Vec::new();
     leaves.resize(v.len(), true);
@@ -16,7 +66,7 @@ fn to_minimal_groebner_basis(v: Vec<Polynomial>) -> Vec<Polynomial> {
         let gs = v
             .iter()
             .enumerate()
-            .filter(|(j, _)| &i != j && leaves[j.clone()])
+            .filter(|(j, _)| &i != j && leaves[*j])
             .map(|(_, g)| g)
             .collect::<Vec<_>>();
 
@@ -49,20 +99,20 @@ fn to_minimal_groebner_basis(v: Vec<Polynomial>) -> Vec<Polynomial> {
         }
     }
 
-    let v = v
-        .into_iter()
+    v.into_iter()
         .enumerate()
-        .filter(|(i, _)| leaves[i.clone()])
+        .filter(|(i, _)| leaves[*i])
         .map(|(_, g)| {
-            assert_ne!(&g, &Polynomial::from((g.get_n(), g.get_monomial_order())));
-            g.normalize()
+            assert_ne!(&g, &Polynomial::new(g.get_n(), g.get_monomial_order()));
+            R::normalize(g)
         })
-        .collect::<Vec<_>>();
-    v
+        .collect::<Vec<_>>()
 }
 
-fn to_reduced_groebner_basis(v: Vec<Polynomial>) -> Vec<Polynomial> {
-    let mut v = to_minimal_groebner_basis(v);
+fn to_reduced_groebner_basis<K: Ring, R: Reduction<K>>(
+    v: Vec<Polynomial<K>>,
+) -> Vec<Polynomial<K>> {
+    let mut v = to_minimal_groebner_basis::<K, R>(v);
 
     loop {
         let mut update_flag = false;
@@ -73,7 +123,7 @@ fn to_reduced_groebner_basis(v: Vec<Polynomial>) -> Vec<Polynomial> {
                 .enumerate()
                 .filter(|(j, _)| &i != j)
                 .map(|(_, g)| {
-                    assert_ne!(g, &Polynomial::from((g.get_n(), g.get_monomial_order())));
+                    assert_ne!(g, &Polynomial::new(g.get_n(), g.get_monomial_order()));
                     g
                 })
                 .collect::<Vec<_>>();
@@ -82,9 +132,9 @@ fn to_reduced_groebner_basis(v: Vec<Polynomial>) -> Vec<Polynomial> {
                 continue;
             }
 
-            let (_, r) = v[i].polynomial_divide_ref(&gs);
+            let r = R::reduce(&v[i], &gs);
 
-            assert_ne!(&r, &Polynomial::from((r.get_n(), r.get_monomial_order())));
+            assert_ne!(&r, &Polynomial::new(r.get_n(), r.get_monomial_order()));
 
             if v[i] != r {
                 update_flag = true;
@@ -97,7 +147,7 @@ fn to_reduced_groebner_basis(v: Vec<Polynomial>) -> Vec<Polynomial> {
         }
     }
 
-    let mut v: Vec<Polynomial> = v.into_iter().map(|f| f.normalize()).collect();
+    let mut v: Vec<Polynomial<K>> = v.into_iter().map(R::normalize).collect();
     v.sort_by(|lhs, rhs| {
         let lm_l = lhs.fetch_lm();
         let lm_r = rhs.fetch_lm();
@@ -130,8 +180,8 @@ struct PolynomialPair {
     ij: (usize, usize),
 }
 
-impl From<(&Vec<Polynomial>, &Vec<Integer>, (usize, usize))> for PolynomialPair {
-    fn from((fs, total_degrees, ij_): (&Vec<Polynomial>, &Vec<Integer>, (usize, usize))) -> Self {
+impl<K: Ring> From<(&[Polynomial<K>], &[Integer], (usize, usize))> for PolynomialPair {
+    fn from((fs, total_degrees, ij_): (&[Polynomial<K>], &[Integer], (usize, usize))) -> Self {
         assert_ne!(ij_.0, ij_.1);
 
         let ij_ = if ij_.0 < ij_.1 { ij_ } else { (ij_.1, ij_.0) };
@@ -187,7 +237,7 @@ impl From<(&Vec<Polynomial>, &Vec<Integer>, (usize, usize))> for PolynomialPair
 
 impl PartialEq for PolynomialPair {
     fn eq(&self, other: &Self) -> bool {
-        self.cmp(&other) == Ordering::Equal
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -207,12 +257,12 @@ impl Ord for PolynomialPair {
 
         let ord_suger = self.s_polynomial_suger.cmp(&other.s_polynomial_suger);
 
-        if &ord_suger != &Ordering::Equal {
+        if ord_suger != Ordering::Equal {
             return ord_suger;
         }
 
         let ord_lcm = self.lcm.cmp(&other.lcm);
-        if &ord_lcm != &Ordering::Equal {
+        if ord_lcm != Ordering::Equal {
             return ord_lcm;
         }
 
@@ -220,19 +270,185 @@ impl Ord for PolynomialPair {
     }
 }
 
-pub fn compute_groebner_basis(fs: Vec<Polynomial>) -> Vec<Polynomial> {
+pub fn compute_groebner_basis<K: Field>(fs: Vec<Polynomial<K>>) -> Vec<Polynomial<K>> {
+    buchberger::<K, FieldReduction<K>>(fs)
+}
+
+/// 整数係数のまま Q 上の簡約グレブナー基底を計算する
+///
+/// 簡約は擬簡約で行い、各元は主係数が正の原始的な整数係数多項式として返す。
+pub fn compute_groebner_basis_fraction_free(
+    fs: Vec<Polynomial<Integer>>,
+) -> Vec<Polynomial<Integer>> {
+    buchberger::<Integer, FractionFreeReduction>(fs)
+}
+
+fn positive_lc(f: Polynomial<Integer>) -> Polynomial<Integer> {
+    match f.fetch_lc() {
+        Some(lc) if lc < Integer::zero() => -f,
+        _ => f,
+    }
+}
+
+// lc(g) lm(g) が lc(f) lm(f) を割り切る
+fn is_strongly_divisible_by(f: &Polynomial<Integer>, g: &Polynomial<Integer>) -> bool {
+    match (f.fetch_lm(), g.fetch_lm(), f.fetch_lc(), g.fetch_lc()) {
+        (Some(lm_f), Some(lm_g), Some(lc_f), Some(lc_g)) => {
+            lm_f.is_divisible_by(&lm_g) && (&lc_f % &lc_g).is_zero()
+        }
+        (_, _, _, _) => panic!("found 0 polynomial"),
+    }
+}
+
+fn to_reduced_strong_groebner_basis(v: Vec<Polynomial<Integer>>) -> Vec<Polynomial<Integer>> {
+    let mut leaves = Vec::new();
+    leaves.resize(v.len(), true);
+
+    for i in 0..v.len() {
+        let is_redundant = (0..v.len()).filter(|j| &i != j && leaves[*j]).any(|j| {
+            // 主項が全く同じ時は先にある方を残す
+            is_strongly_divisible_by(&v[i], &v[j])
+                && (j < i || !is_strongly_divisible_by(&v[j], &v[i]))
+        });
+        if is_redundant {
+            leaves[i] = false;
+        }
+    }
+
+    let mut v = v
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| leaves[*i])
+        .map(|(_, g)| g)
+        .collect::<Vec<_>>();
+
+    loop {
+        let mut update_flag = false;
+
+        for i in 0..(v.len()) {
+            let gs = v
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| &i != j)
+                .map(|(_, g)| g)
+                .collect::<Vec<_>>();
+
+            let r = positive_lc(v[i].strong_normal_form(&gs));
+
+            if v[i] != r {
+                update_flag = true;
+                v[i] = r;
+            }
+        }
+
+        if !update_flag {
+            break;
+        }
+    }
+
+    v.sort_by(|lhs, rhs| match (lhs.fetch_lm(), rhs.fetch_lm()) {
+        (Some(lm_l), Some(lm_r)) => lm_r.cmp(&lm_l), // revert
+        (_, _) => panic!("found 0 polynomial"),
+    }); // 出力は降順で
+    v
+}
+
+/// Z 上の強グレブナー基底を計算する
+///
+/// 各対について S 多項式と GCD 多項式を作り、係数も含めて簡約する。
+/// 返す基底は主項が係数込みで互いに割り切れず、主係数が正で、主項の降順に並ぶ。
+pub fn compute_strong_groebner_basis(fs: Vec<Polynomial<Integer>>) -> Vec<Polynomial<Integer>> {
+    if fs.is_empty() {
+        return Vec::new();
+    }
+    let zero_polynomial = Polynomial::new(fs[0].get_n(), fs[0].get_monomial_order());
+
+    let mut fs = fs
+        .into_iter()
+        .filter(|f| f != &zero_polynomial)
+        .map(positive_lc)
+        .collect::<Vec<Polynomial<Integer>>>();
+
+    let mut total_degrees = fs
+        .iter()
+        .map(|f| match f.fetch_total_degree() {
+            Some(total_degree) => total_degree,
+            None => panic!("found 0 polynomial"),
+        })
+        .collect::<Vec<Integer>>();
+
+    let t = fs.len();
+    let mut pairs = (0..t)
+        .flat_map(|i| {
+            ((i + 1)..t)
+                .map(|j| PolynomialPair::from((fs.as_slice(), total_degrees.as_slice(), (i, j))))
+                .collect::<Vec<PolynomialPair>>()
+        })
+        .collect::<BTreeSet<PolynomialPair>>();
+
+    while let Some(polynomial_pair) = pairs.iter().next().cloned() {
+        pairs.remove(&polynomial_pair);
+
+        let fi = &fs[polynomial_pair.ij.0];
+        let fj = &fs[polynomial_pair.ij.1];
+
+        let mut hs = vec![polynomial::s_polynomial_fraction_free(fi, fj)];
+
+        // 主係数の一方が他方を割り切る時は GCD 多項式は不要
+        if let (Some(lc_i), Some(lc_j)) = (fi.fetch_lc(), fj.fetch_lc()) {
+            if !(&lc_i % &lc_j).is_zero() && !(&lc_j % &lc_i).is_zero() {
+                hs.push(polynomial::g_polynomial(fi, fj));
+            }
+        }
+
+        for h in hs.into_iter().flatten() {
+            let h = h.strong_normal_form(&fs.iter().collect::<Vec<_>>());
+            if h == zero_polynomial {
+                continue;
+            }
+
+            let h = positive_lc(h);
+            let total_degree = match h.fetch_total_degree() {
+                Some(total_degree) => total_degree,
+                None => panic!("0 polynomial found"),
+            };
+
+            let t = fs.len();
+            fs.push(h);
+            total_degrees.push(total_degree);
+
+            for i in 0..t {
+                pairs.insert(PolynomialPair::from((
+                    fs.as_slice(),
+                    total_degrees.as_slice(),
+                    (i, t),
+                )));
+            }
+        }
+    }
+
+    to_reduced_strong_groebner_basis(fs)
+}
+
+fn buchberger<K: Ring, R: Reduction<K>>(fs: Vec<Polynomial<K>>) -> Vec<Polynomial<K>> {
     if fs.is_empty() {
         return Vec::new();
     }
     let monomial_order = fs[0].get_monomial_order();
     let n = fs[0].get_n();
+    // 整列順序でなければ停止しない
+    assert!(
+        monomial_order.is_global(),
+        "monomial order must be a well-ordering: {}",
+        monomial_order
+    );
 
-    let zero_polynomial = Polynomial::from((n, monomial_order));
+    let zero_polynomial = Polynomial::new(n, monomial_order);
 
     let fs = fs
         .into_iter()
         .filter(|f| f != &zero_polynomial)
-        .collect::<Vec<Polynomial>>();
+        .collect::<Vec<Polynomial<K>>>();
 
     let mut fs = fs;
     //fs.sort(); // TODO
@@ -251,17 +467,12 @@ pub fn compute_groebner_basis(fs: Vec<Polynomial>) -> Vec<Polynomial> {
         })
         .collect::<Vec<Integer>>();
 
-    let mut pairs = fs
-        .iter()
-        .enumerate()
-        .map(|(i, _)| {
-            fs.iter()
-                .enumerate()
-                .filter(|(j, _)| &i < j)
-                .map(|(j, _)| PolynomialPair::from((&fs, &total_degrees, (i.clone(), j.clone()))))
+    let mut pairs = (0..t)
+        .flat_map(|i| {
+            ((i + 1)..t)
+                .map(|j| PolynomialPair::from((fs.as_slice(), total_degrees.as_slice(), (i, j))))
                 .collect::<Vec<PolynomialPair>>()
         })
-        .flatten()
         .collect::<BTreeSet<PolynomialPair>>();
 
     assert_eq!(pairs.len(), t * (t - 1) / 2);
@@ -273,8 +484,7 @@ pub fn compute_groebner_basis(fs: Vec<Polynomial>) -> Vec<Polynomial> {
             Some(polynomial_pair) => {
                 let (i, j) = &polynomial_pair.ij;
 
-                let condition0 =
-                    polynomial_pair.lcm == &polynomial_pair.lm_fi * &polynomial_pair.lm_fj;
+                let condition0 = polynomial_pair.lm_fi.is_coprime(&polynomial_pair.lm_fj);
                 if condition0 {
                     (polynomial_pair.clone(), true)
                 } else {
@@ -289,10 +499,16 @@ pub fn compute_groebner_basis(fs: Vec<Polynomial>) -> Vec<Polynomial> {
                             }
                         })
                         .any(|(k, _)| {
-                            let s_ik =
-                                PolynomialPair::from((&fs, &total_degrees, (i.clone(), k.clone())));
-                            let s_jk =
-                                PolynomialPair::from((&fs, &total_degrees, (j.clone(), k.clone())));
+                            let s_ik = PolynomialPair::from((
+                                fs.as_slice(),
+                                total_degrees.as_slice(),
+                                (*i, k),
+                            ));
+                            let s_jk = PolynomialPair::from((
+                                fs.as_slice(),
+                                total_degrees.as_slice(),
+                                (*j, k),
+                            ));
                             match (pairs.get(&s_ik), pairs.get(&s_jk)) {
                                 (None, None) => true,
                                 (_, _) => false,
@@ -310,10 +526,10 @@ pub fn compute_groebner_basis(fs: Vec<Polynomial>) -> Vec<Polynomial> {
             let fi = &fs[polynomial_pair.ij.0];
             let fj = &fs[polynomial_pair.ij.1];
 
-            let s = polynomial::s_polynomial(fi, fj);
+            let s = R::s_polynomial(fi, fj);
 
             if let Some(s) = s {
-                let (_, s) = s.polynomial_divide(&fs);
+                let s = R::reduce(&s, &fs.iter().collect::<Vec<_>>());
                 if s != zero_polynomial {
                     let ft = s;
                     let total_degree = ft.fetch_total_degree();
@@ -324,10 +540,14 @@ pub fn compute_groebner_basis(fs: Vec<Polynomial>) -> Vec<Polynomial> {
                             total_degrees.push(total_degree);
 
                             for i in 0..t {
-                                pairs.insert(PolynomialPair::from((&fs, &total_degrees, (i, t))));
+                                pairs.insert(PolynomialPair::from((
+                                    fs.as_slice(),
+                                    total_degrees.as_slice(),
+                                    (i, t),
+                                )));
                             }
 
-                            t = t + 1;
+                            t += 1;
                         }
                         None => {
                             panic!("0 polynomial found");
@@ -339,7 +559,7 @@ pub fn compute_groebner_basis(fs: Vec<Polynomial>) -> Vec<Polynomial> {
         pairs.remove(&polynomial_pair);
     }
 
-    to_reduced_groebner_basis(fs)
+    to_reduced_groebner_basis::<K, R>(fs)
 }
 
 mod test;
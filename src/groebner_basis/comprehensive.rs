@@ -0,0 +1,254 @@
+use crate::groebner_basis::{compute_groebner_basis, to_reduced_groebner_basis, FieldReduction};
+use crate::monomial::{Monomial, MonomialHandlers, MonomialOrder};
+use crate::polynomial::{Polynomial, PolynomialHandlers};
+use crate::scalar::{Integer, Rational, RationalFunction, Ring};
+
+/// 包括的グレブナー系の1つの区画
+///
+/// パラメータが equations を全て満たし、nonequations のどれも0にしない時、
+/// 元のイデアルにパラメータを代入したものの簡約グレブナー基底は basis にパラメータを代入したものになる。
+/// basis の係数の分母は区画上で0にならない。
+#[derive(Clone, Debug)]
+pub struct ParametricCell {
+    /// パラメータ t_1, ..., t_k の多項式 (= 0)
+    pub equations: Vec<Polynomial>,
+    /// パラメータ t_1, ..., t_k の多項式 (!= 0)
+    pub nonequations: Vec<Polynomial>,
+    /// 変数 x_1, ..., x_n の多項式 (係数は Q(t_1, ..., t_k))
+    pub basis: Vec<Polynomial<RationalFunction>>,
+}
+
+fn exponents(m: &Monomial) -> Vec<Integer> {
+    m.clone().into()
+}
+
+// n + k 変数の多項式のうち x_1, ..., x_n を含まないものをパラメータだけの多項式にする
+fn to_parameter_polynomial(f: &Polynomial, n: usize) -> Option<Polynomial> {
+    let mut ret = Polynomial::new(f.get_n() - n, MonomialOrder::Lex);
+    for (m, c) in f.terms() {
+        let v = exponents(m);
+        if v[..n].iter().any(|e| !e.is_zero()) {
+            return None;
+        }
+        ret.add_term(
+            c.clone(),
+            Monomial::from((v[n..].to_vec(), MonomialOrder::Lex)),
+        );
+    }
+    Some(ret)
+}
+
+// パラメータの多項式を n 個の変数の後ろに並べた n + k 変数の多項式にする
+fn from_parameter_polynomial(f: &Polynomial, n: usize) -> Polynomial {
+    let mut ret = Polynomial::new(n + f.get_n(), MonomialOrder::Lex);
+    for (m, c) in f.terms() {
+        let mut v = vec![Integer::zero(); n];
+        v.extend(exponents(m));
+        ret.add_term(c.clone(), Monomial::from((v, MonomialOrder::Lex)));
+    }
+    ret
+}
+
+// n + k 変数の多項式を係数が Q(t_1, ..., t_k) の n 変数多項式とみなす
+fn to_parametric_polynomial(f: &Polynomial, n: usize) -> Polynomial<RationalFunction> {
+    let k = f.get_n() - n;
+    let mut ret = Polynomial::new(n, MonomialOrder::Lex);
+    for (m, c) in f.terms() {
+        let v = exponents(m);
+        let t = Polynomial::from((
+            c.clone(),
+            Monomial::from((v[n..].to_vec(), MonomialOrder::Lex)),
+            MonomialOrder::Lex,
+        ));
+        debug_assert_eq!(t.get_n(), k);
+        ret.add_term(
+            RationalFunction::from(t),
+            Monomial::from((v[..n].to_vec(), MonomialOrder::Lex)),
+        );
+    }
+    ret
+}
+
+fn is_constant(f: &Polynomial) -> bool {
+    f.fetch_total_degree() == Some(Integer::zero())
+}
+
+// V(es) \ V(ns の積) が (代数閉包上で) 空でないか (Rabinowitsch trick)
+fn is_consistent(es: &[Polynomial], ns: &[Polynomial], k: usize) -> bool {
+    // 1 - z ns[0] ns[1] ... (z は先頭の変数)
+    let mut v = vec![0; k + 1];
+    v[0] = 1;
+    let z = Polynomial::from((
+        Rational::one(),
+        Monomial::from((v, MonomialOrder::Lex)),
+        MonomialOrder::Lex,
+    ));
+    let one = Polynomial::from((Rational::one(), k + 1, MonomialOrder::Lex));
+    let zn = ns
+        .iter()
+        .fold(z, |zn, f| &zn * &from_parameter_polynomial(f, 1));
+
+    let mut fs = es
+        .iter()
+        .map(|e| from_parameter_polynomial(e, 1))
+        .collect::<Vec<_>>();
+    fs.push(&one - &zn);
+
+    !compute_groebner_basis(fs).iter().any(is_constant)
+}
+
+fn simplify(es: Vec<Polynomial>) -> Vec<Polynomial> {
+    if es.is_empty() {
+        es
+    } else {
+        compute_groebner_basis(es)
+    }
+}
+
+fn unit_basis(n: usize) -> Vec<Polynomial<RationalFunction>> {
+    vec![Polynomial::from((
+        RationalFunction::one(),
+        n,
+        MonomialOrder::Lex,
+    ))]
+}
+
+// Suzuki-Sato のアルゴリズム (区画が交わらないように分ける)
+fn comprehensive_groebner_system_main(
+    es: Vec<Polynomial>,
+    ns: Vec<Polynomial>,
+    fs: Vec<Polynomial>,
+    n: usize,
+    k: usize,
+    cells: &mut Vec<ParametricCell>,
+) {
+    if !is_consistent(&es, &ns, k) {
+        return;
+    }
+
+    let mut input = fs;
+    input.extend(es.iter().map(|e| from_parameter_polynomial(e, n)));
+    let gs = compute_groebner_basis(input);
+
+    if gs.iter().any(is_constant) {
+        cells.push(ParametricCell {
+            equations: es,
+            nonequations: ns,
+            basis: unit_basis(n),
+        });
+        return;
+    }
+
+    // G ∩ Q[t] とそれ以外
+    let (gs_r, gs_x): (Vec<_>, Vec<_>) = gs
+        .iter()
+        .map(|g| (to_parameter_polynomial(g, n), g))
+        .partition(|(g_r, _)| g_r.is_some());
+    let gs_r = gs_r
+        .into_iter()
+        .map(|(g_r, _)| g_r.unwrap())
+        .collect::<Vec<_>>();
+
+    // G ∩ Q[t] のどれかが0でなければ単位イデアル
+    for (j, g_r) in gs_r.iter().enumerate() {
+        let mut es_j = es.clone();
+        es_j.extend(gs_r[..j].iter().cloned());
+        let mut ns_j = ns.clone();
+        ns_j.push(g_r.clone());
+        if is_consistent(&es_j, &ns_j, k) {
+            cells.push(ParametricCell {
+                equations: simplify(es_j),
+                nonequations: ns_j,
+                basis: unit_basis(n),
+            });
+        }
+    }
+
+    // x についての主項で極小なもの
+    let gs_x = gs_x
+        .into_iter()
+        .map(|(_, g)| to_parametric_polynomial(g, n))
+        .collect::<Vec<_>>();
+    let lms = gs_x
+        .iter()
+        .map(|g| g.fetch_lm().unwrap())
+        .collect::<Vec<_>>();
+    let gs_m = gs_x
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            !lms.iter()
+                .enumerate()
+                .any(|(j, lm)| j != *i && lms[*i].is_divisible_by(lm) && (&lms[*i] != lm || j < *i))
+        })
+        .map(|(_, g)| g.clone())
+        .collect::<Vec<_>>();
+
+    // 主係数 (パラメータの多項式)
+    let hs = gs_m
+        .iter()
+        .map(|g| g.fetch_lc().unwrap().numerator().clone())
+        .filter(|h| !is_constant(h))
+        .collect::<Vec<_>>();
+
+    // 主係数が全て0でない区画
+    let mut ns_h = ns.clone();
+    ns_h.extend(hs.iter().cloned());
+    if is_consistent(&gs_r, &ns_h, k) {
+        cells.push(ParametricCell {
+            equations: gs_r.clone(),
+            nonequations: ns_h,
+            basis: to_reduced_groebner_basis::<_, FieldReduction<_>>(gs_m),
+        });
+    }
+
+    // h_1, ..., h_{i-1} が0でなく h_i が0となる区画
+    for (i, h) in hs.iter().enumerate() {
+        let mut es_i = gs_r.clone();
+        es_i.push(h.clone());
+        let mut ns_i = ns.clone();
+        ns_i.extend(hs[..i].iter().cloned());
+        comprehensive_groebner_system_main(es_i, ns_i, gs.clone(), n, k, cells);
+    }
+}
+
+/// 包括的グレブナー系
+///
+/// fs は n + k 変数の多項式で、先頭の n 個 x_1, ..., x_n を変数、残りの k 個 t_1, ..., t_k をパラメータとみなす。
+/// パラメータ空間を交わらない区画に分け、それぞれの区画で有効な簡約グレブナー基底を返す。
+/// 計算は x >> t の辞書式順序で行い、基底も辞書式順序で返す。
+/// n が多項式の変数の数より大きい時は panic する。
+pub fn comprehensive_groebner_system(fs: Vec<Polynomial>, n: usize) -> Vec<ParametricCell> {
+    let k = match fs.first() {
+        Some(f) => {
+            assert!(
+                n <= f.get_n(),
+                "number of main variables ({}) exceeds number of variables ({})",
+                n,
+                f.get_n()
+            );
+            f.get_n() - n
+        }
+        None => return Vec::new(),
+    };
+
+    // 辞書式順序に並べ直す
+    let fs = fs
+        .iter()
+        .map(|f| {
+            assert_eq!(f.get_n(), n + k);
+            let mut g = Polynomial::new(n + k, MonomialOrder::Lex);
+            for (m, c) in f.terms() {
+                g.add_term(
+                    c.clone(),
+                    Monomial::from((exponents(m), MonomialOrder::Lex)),
+                );
+            }
+            g
+        })
+        .collect();
+
+    let mut cells = Vec::new();
+    comprehensive_groebner_system_main(Vec::new(), Vec::new(), fs, n, k, &mut cells);
+    cells
+}
@@ -0,0 +1,86 @@
+use crate::groebner_basis::compute_groebner_basis;
+use crate::monomial::{Monomial, MonomialOrder};
+use crate::polynomial::{Polynomial, PolynomialHandlers, PolynomialRing};
+use crate::scalar::Field;
+
+// 指数を order の順に並べ替えた単項式
+fn permute(m: &Monomial, order: &[usize], monomial_order: &MonomialOrder) -> Monomial {
+    let e = m.exponents();
+    let v = order.iter().map(|&i| e[i] as i64).collect::<Vec<_>>();
+    Monomial::from((v, monomial_order.clone()))
+}
+
+/// 消去イデアル
+///
+/// fs の生成するイデアルと、vars_to_remove (0始まりの添字) 以外の変数からなる多項式環の共通部分の生成元を返す。
+/// 消す変数と残す変数をそれぞれ次数逆辞書式順序としたブロック順序で計算し、
+/// 残す変数を元の順に並べた多項式 (次数逆辞書式順序での簡約グレブナー基底) を返す。
+/// fs が変数名を持つ環の多項式なら、結果は残す変数の名前を持つ環の多項式になる。
+pub fn eliminate<K: Field>(fs: Vec<Polynomial<K>>, vars_to_remove: &[usize]) -> Vec<Polynomial<K>> {
+    let n = match fs.first() {
+        Some(f) => f.get_n(),
+        None => return Vec::new(),
+    };
+    assert!(vars_to_remove.iter().all(|&i| i < n), "no such variable");
+
+    let keep = (0..n)
+        .filter(|i| !vars_to_remove.contains(i))
+        .collect::<Vec<_>>();
+    let k = n - keep.len();
+
+    let ring = fs[0].ring().map(|ring| {
+        let names = keep
+            .iter()
+            .map(|&i| ring.names()[i].as_str())
+            .collect::<Vec<_>>();
+        PolynomialRing::new(&names, MonomialOrder::Grevlex)
+    });
+
+    // 消す変数を先頭に集める
+    let mut order = (0..n)
+        .filter(|i| vars_to_remove.contains(i))
+        .collect::<Vec<_>>();
+    order.extend(keep.iter().cloned());
+
+    let blocks = [(k, MonomialOrder::Grevlex), (n - k, MonomialOrder::Grevlex)]
+        .iter()
+        .filter(|(size, _)| *size > 0)
+        .cloned()
+        .collect::<Vec<_>>();
+    let block_order = if blocks.is_empty() {
+        MonomialOrder::Grevlex
+    } else {
+        MonomialOrder::block(blocks)
+    };
+
+    let fs = fs
+        .iter()
+        .map(|f| {
+            assert_eq!(f.get_n(), n);
+            let mut g = Polynomial::new(n, block_order.clone());
+            for (m, c) in f.terms() {
+                g.add_term(c.clone(), permute(m, &order, &block_order));
+            }
+            g
+        })
+        .collect::<Vec<_>>();
+
+    let tail = (k..n).collect::<Vec<_>>();
+    compute_groebner_basis(fs)
+        .iter()
+        .filter(|g| {
+            g.terms()
+                .all(|(m, _)| m.exponents()[..k].iter().all(|&e| e == 0))
+        })
+        .map(|g| {
+            let mut h = match &ring {
+                Some(ring) => ring.zero(),
+                None => Polynomial::new(n - k, MonomialOrder::Grevlex),
+            };
+            for (m, c) in g.terms() {
+                h.add_term(c.clone(), permute(m, &tail, &MonomialOrder::Grevlex));
+            }
+            h
+        })
+        .collect()
+}
@@ -0,0 +1,253 @@
+use crate::groebner_basis::compute_groebner_basis;
+use crate::monomial::Monomial;
+use crate::polynomial::{Polynomial, PolynomialHandlers};
+use crate::scalar::{self, Fp, Integer};
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+// 2^62 未満の素数を大きい順に
+const PRIMES: [u64; 16] = [
+    4_611_686_018_427_387_847,
+    4_611_686_018_427_387_817,
+    4_611_686_018_427_387_787,
+    4_611_686_018_427_387_761,
+    4_611_686_018_427_387_751,
+    4_611_686_018_427_387_737,
+    4_611_686_018_427_387_733,
+    4_611_686_018_427_387_709,
+    4_611_686_018_427_387_701,
+    4_611_686_018_427_387_631,
+    4_611_686_018_427_387_617,
+    4_611_686_018_427_387_587,
+    4_611_686_018_427_387_461,
+    4_611_686_018_427_387_421,
+    4_611_686_018_427_387_409,
+    4_611_686_018_427_387_329,
+];
+
+// GF(p) 上の簡約グレブナー基底 (各多項式の項は降順)
+type ModularImage = Vec<Vec<(Monomial, u64)>>;
+
+fn modular_image<const P: u64>(fs: &[Polynomial]) -> Option<ModularImage> {
+    let fs_p = fs
+        .iter()
+        .map(|f| f.try_map_coeff(|c| Fp::<P>::try_from(c)))
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+
+    // 主係数が p で消えると主項が変わるので使わない
+    if fs
+        .iter()
+        .zip(fs_p.iter())
+        .any(|(f, f_p)| f.fetch_lm() != f_p.fetch_lm())
+    {
+        return None;
+    }
+
+    let gs = compute_groebner_basis(fs_p);
+
+    Some(
+        gs.iter()
+            .map(|g| {
+                g.terms()
+                    .rev()
+                    .map(|(monomial, coeff)| (monomial.clone(), coeff.value()))
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+macro_rules! modular_image_dispatch {
+    ($k:expr, $fs:expr, $($i:literal)*) => {
+        match $k {
+            $($i => modular_image::<{ PRIMES[$i] }>($fs),)*
+            _ => unreachable!(),
+        }
+    };
+}
+
+// 主項の並びが同じ像を中国剰余定理で合成したもの
+struct Accumulator {
+    lms: Vec<Monomial>,
+    modulus: Integer,
+    coeffs: Vec<BTreeMap<Monomial, Integer>>,
+    count: usize,
+}
+
+impl Accumulator {
+    fn add(&mut self, image: &ModularImage, p: u64) {
+        let p = Integer::from(p);
+        for (acc, g) in self.coeffs.iter_mut().zip(image.iter()) {
+            let mut residues = g
+                .iter()
+                .map(|(monomial, c)| (monomial.clone(), Integer::from(*c)))
+                .collect::<BTreeMap<Monomial, Integer>>();
+            for monomial in acc.keys() {
+                residues
+                    .entry(monomial.clone())
+                    .or_insert_with(Integer::zero);
+            }
+
+            for (monomial, b) in residues {
+                let a = acc.remove(&monomial).unwrap_or_else(Integer::zero);
+                acc.insert(monomial, scalar::crt(&a, &self.modulus, &b, &p));
+            }
+        }
+        self.modulus = &self.modulus * &p;
+        self.count += 1;
+    }
+
+    fn reconstruct(&self, fs: &[Polynomial]) -> Option<Vec<Polynomial>> {
+        let zero = match fs[0].ring() {
+            Some(ring) => ring.zero(),
+            None => Polynomial::new(fs[0].get_n(), fs[0].get_monomial_order()),
+        };
+
+        self.coeffs
+            .iter()
+            .map(|acc| {
+                let mut g = zero.clone();
+                for (monomial, a) in acc {
+                    let c = scalar::rational_reconstruction(a, &self.modulus)?;
+                    g.add_term(c, monomial.clone());
+                }
+                Some(g)
+            })
+            .collect()
+    }
+}
+
+// 入力の各多項式が G で 0 に簡約される (I ⊆ <G>) ことと、G の S 多項式が全て 0 に簡約される
+// (G は <G> のグレブナー基底) ことを Q 上で確かめる。<G> ⊆ I は確かめないので、
+// これを通っても G が I のグレブナー基底だとは限らない
+fn verify(fs: &[Polynomial], gs: &[Polynomial]) -> bool {
+    let n = fs[0].get_n();
+    let monomial_order = fs[0].get_monomial_order();
+    let zero = Polynomial::new(n, monomial_order);
+
+    if fs.iter().any(|f| f.polynomial_divide(gs).1 != zero) {
+        return false;
+    }
+
+    for (i, gi) in gs.iter().enumerate() {
+        for gj in gs.iter().skip(i + 1) {
+            if let (Some(lm_i), Some(lm_j)) = (gi.fetch_lm(), gj.fetch_lm()) {
+                // 主項が互いに素なら S 多項式は 0 に簡約される
+                if lm_i.is_coprime(&lm_j) {
+                    continue;
+                }
+            }
+            if let Some(s) = crate::polynomial::s_polynomial(gi, gj) {
+                if s.polynomial_divide(gs).1 != zero {
+                    return false;
+                }
+            }
+        }
+    }
+
+    true
+}
+
+/// compute_groebner_basis_modular の結果がどのように得られたか
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModularStatus {
+    /// 復元結果が素数を1つ増やしても変わらなかったので採用した (検証はしていない)
+    Unverified,
+    /// さらに入力が結果で 0 に簡約され、結果の S 多項式も 0 に簡約されることを Q 上で確かめた
+    /// (結果の各多項式が入力のイデアルに入ることは確かめていない)
+    Checked,
+    /// 素数を使い切ったので Q 上で直接計算した (入力のイデアルの簡約グレブナー基底)
+    Fallback,
+}
+
+/// 複数の素数 p を法として簡約グレブナー基底を計算し、中国剰余定理と有理数復元で
+/// Q 上の簡約グレブナー基底を求める
+///
+/// 主項の並びが多数派と異なる像は unlucky な素数によるものとして捨てる。
+/// 復元結果が素数を1つ増やしても変わらなくなった時点で採用し、`verify_result` が
+/// true の時はさらに入力 I と結果 G について I ⊆ <G> と G の S 多項式が 0 に簡約されることを
+/// Q 上で確かめる (<G> ⊆ I は確かめない)。素数を使い切った場合は Q 上で直接計算する。
+/// どれで得られたかを ModularStatus で返す。
+pub fn compute_groebner_basis_modular(
+    fs: Vec<Polynomial>,
+    verify_result: bool,
+) -> (Vec<Polynomial>, ModularStatus) {
+    let zero_polynomial = match fs.first() {
+        Some(f) => Polynomial::new(f.get_n(), f.get_monomial_order()),
+        // 0 のイデアルの基底は空で、確かめるまでもない
+        None => return (Vec::new(), ModularStatus::Checked),
+    };
+
+    let fs = fs
+        .into_iter()
+        .filter(|f| f != &zero_polynomial)
+        .collect::<Vec<Polynomial>>();
+
+    if fs.is_empty() {
+        return (Vec::new(), ModularStatus::Checked);
+    }
+
+    let mut accumulators: Vec<Accumulator> = Vec::new();
+    let mut last: Option<Vec<Polynomial>> = None;
+
+    for (k, &p) in PRIMES.iter().enumerate() {
+        let image = modular_image_dispatch!(k, &fs, 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15);
+        let image = match image {
+            Some(image) => image,
+            None => continue,
+        };
+
+        let lms = image
+            .iter()
+            .map(|g| g[0].0.clone())
+            .collect::<Vec<Monomial>>();
+
+        let position = accumulators.iter().position(|acc| acc.lms == lms);
+        let position = match position {
+            Some(position) => position,
+            None => {
+                accumulators.push(Accumulator {
+                    coeffs: vec![BTreeMap::new(); lms.len()],
+                    lms,
+                    modulus: Integer::one(),
+                    count: 0,
+                });
+                accumulators.len() - 1
+            }
+        };
+        accumulators[position].add(&image, p);
+
+        // 多数派の像だけを使う
+        let best = accumulators
+            .iter()
+            .enumerate()
+            .max_by(|(i, lhs), (j, rhs)| lhs.count.cmp(&rhs.count).then(j.cmp(i)))
+            .map(|(i, _)| i);
+        if best != Some(position) {
+            continue;
+        }
+
+        let gs = accumulators[position].reconstruct(&fs);
+        let gs = match gs {
+            Some(gs) => gs,
+            None => {
+                last = None;
+                continue;
+            }
+        };
+
+        if last.as_ref() == Some(&gs) {
+            if !verify_result {
+                return (gs, ModularStatus::Unverified);
+            }
+            if verify(&fs, &gs) {
+                return (gs, ModularStatus::Checked);
+            }
+        }
+        last = Some(gs);
+    }
+
+    (compute_groebner_basis(fs), ModularStatus::Fallback)
+}
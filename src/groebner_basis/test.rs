@@ -4,13 +4,18 @@ use crate::groebner_basis;
 #[allow(unused_imports)]
 use crate::monomial;
 #[allow(unused_imports)]
-use crate::monomial::{Monomial, MonomialHandlers};
+use crate::monomial::{Monomial, MonomialHandlers, MonomialOrdering};
 #[allow(unused_imports)]
 use crate::polynomial;
 #[allow(unused_imports)]
 use crate::polynomial::{Polynomial, PolynomialHandlers};
 #[allow(unused_imports)]
-use crate::scalar::{Integer, Rational};
+use crate::scalar::{
+    AlgebraicNumber, Field, FiniteField, Fp, Fq, Integer, NumberField, Rational, RationalFunction,
+    Ring,
+};
+#[allow(unused_imports)]
+use std::convert::TryFrom;
 
 #[allow(dead_code)]
 fn check_reduced_groebner_basis_property(fs: &Vec<Polynomial>, gs: &Vec<Polynomial>) {
@@ -147,3 +152,761 @@ fn test_groebner_basis() {
     assert_eq!(gb[1], correct_g1);
     assert_eq!(gb[2], correct_g2);
 }
+
+#[test]
+fn test_groebner_basis_fp() {
+    type F = Fp<1_000_000_007>;
+
+    // x^2 + y^2 + z^2 - 1, x^2 + z^2 - y, x - z
+    let mut f0 = Polynomial::from((3, monomial::MonomialOrder::Lex));
+    f0.add_term(Rational::from(1), Monomial::from(vec![2, 0, 0]));
+    f0.add_term(Rational::from(1), Monomial::from(vec![0, 2, 0]));
+    f0.add_term(Rational::from(1), Monomial::from(vec![0, 0, 2]));
+    f0.add_term(Rational::from(-1), Monomial::from(vec![0, 0, 0]));
+
+    let mut f1 = Polynomial::from((3, monomial::MonomialOrder::Lex));
+    f1.add_term(Rational::from(1), Monomial::from(vec![2, 0, 0]));
+    f1.add_term(Rational::from(1), Monomial::from(vec![0, 0, 2]));
+    f1.add_term(Rational::from(-1), Monomial::from(vec![0, 1, 0]));
+
+    let mut f2 = Polynomial::from((3, monomial::MonomialOrder::Lex));
+    f2.add_term(Rational::from(1), Monomial::from(vec![1, 0, 0]));
+    f2.add_term(Rational::from(-1), Monomial::from(vec![0, 0, 1]));
+
+    let fs = vec![f0, f1, f2];
+
+    let gb = groebner_basis::compute_groebner_basis(fs.clone());
+
+    let fs_p = fs
+        .iter()
+        .map(|f| f.try_map_coeff(|c| F::try_from(c)).unwrap())
+        .collect::<Vec<_>>();
+    let gb_p = groebner_basis::compute_groebner_basis(fs_p);
+
+    // 係数の分母がpで割り切れないので、Q上の簡約グレブナー基底の像と一致する
+    assert_eq!(gb.len(), gb_p.len());
+    for (g, g_p) in gb.iter().zip(gb_p.iter()) {
+        assert_eq!(&g.try_map_coeff(|c| F::try_from(c)).unwrap(), g_p);
+    }
+}
+
+#[test]
+fn test_groebner_basis_fp_unit_ideal() {
+    type F = Fp<2_305_843_009_213_693_951>;
+
+    // x*y - 1, x, y^2 + 1 は 1 を生成する
+    let mut f0 = Polynomial::<F>::new(2, monomial::MonomialOrder::Grlex);
+    f0.add_term(F::one(), Monomial::from(vec![1, 1]));
+    f0.add_term(-F::one(), Monomial::from(vec![0, 0]));
+
+    let mut f1 = Polynomial::<F>::new(2, monomial::MonomialOrder::Grlex);
+    f1.add_term(F::one(), Monomial::from(vec![1, 0]));
+
+    let mut f2 = Polynomial::<F>::new(2, monomial::MonomialOrder::Grlex);
+    f2.add_term(F::one(), Monomial::from(vec![0, 2]));
+    f2.add_term(F::one(), Monomial::from(vec![0, 0]));
+
+    let gb = groebner_basis::compute_groebner_basis(vec![f0, f1, f2]);
+
+    assert_eq!(
+        gb,
+        vec![Polynomial::from((
+            F::one(),
+            2,
+            monomial::MonomialOrder::Grlex
+        ))]
+    );
+}
+
+#[test]
+fn test_groebner_basis_modular() {
+    // 3y^2 + 2zw - 2xy, 2yw - 2xz, 2yz - 2w - 2xw, y^2 + z^2 + w^2 - 1
+    let mut f0 = Polynomial::from((4, monomial::MonomialOrder::Lex));
+    f0.add_term(Rational::from(3), Monomial::from(vec![0, 2, 0, 0]));
+    f0.add_term(Rational::from(2), Monomial::from(vec![0, 0, 1, 1]));
+    f0.add_term(Rational::from(-2), Monomial::from(vec![1, 1, 0, 0]));
+
+    let mut f1 = Polynomial::from((4, monomial::MonomialOrder::Lex));
+    f1.add_term(Rational::from(2), Monomial::from(vec![0, 1, 0, 1]));
+    f1.add_term(Rational::from(-2), Monomial::from(vec![1, 0, 1, 0]));
+
+    let mut f2 = Polynomial::from((4, monomial::MonomialOrder::Lex));
+    f2.add_term(Rational::from(2), Monomial::from(vec![0, 1, 1, 0]));
+    f2.add_term(Rational::from(-2), Monomial::from(vec![0, 0, 0, 1]));
+    f2.add_term(Rational::from(-2), Monomial::from(vec![1, 0, 0, 1]));
+
+    let mut f3 = Polynomial::from((4, monomial::MonomialOrder::Lex));
+    f3.add_term(Rational::from(1), Monomial::from(vec![0, 2, 0, 0]));
+    f3.add_term(Rational::from(1), Monomial::from(vec![0, 0, 2, 0]));
+    f3.add_term(Rational::from(1), Monomial::from(vec![0, 0, 0, 2]));
+    f3.add_term(Rational::from(-1), Monomial::from(vec![0, 0, 0, 0]));
+
+    let fs = vec![f0, f1, f2, f3];
+
+    let gb = groebner_basis::compute_groebner_basis(fs.clone());
+    let (gb_modular, status) = groebner_basis::compute_groebner_basis_modular(fs.clone(), true);
+
+    check_reduced_groebner_basis_property(&fs, &gb_modular);
+    assert_eq!(gb, gb_modular);
+    assert_eq!(status, groebner_basis::ModularStatus::Checked);
+    assert_eq!(
+        groebner_basis::compute_groebner_basis_modular(fs, false),
+        (gb, groebner_basis::ModularStatus::Unverified)
+    );
+
+    // 0 のイデアル
+    assert_eq!(
+        groebner_basis::compute_groebner_basis_modular(vec![Polynomial::from(4)], true),
+        (Vec::new(), groebner_basis::ModularStatus::Checked)
+    );
+    assert_eq!(
+        groebner_basis::compute_groebner_basis_modular(Vec::new(), true),
+        (Vec::new(), groebner_basis::ModularStatus::Checked)
+    );
+}
+
+#[test]
+fn test_groebner_basis_modular_unit_ideal() {
+    // x*y - 1, x^2 - 2/3, y^2 - 3/5 は 1 を生成する
+    let mut f0 = Polynomial::from((2, monomial::MonomialOrder::Grlex));
+    f0.add_term(Rational::from(1), Monomial::from(vec![1, 1]));
+    f0.add_term(Rational::from(-1), Monomial::from(vec![0, 0]));
+
+    let mut f1 = Polynomial::from((2, monomial::MonomialOrder::Grlex));
+    f1.add_term(Rational::from(1), Monomial::from(vec![2, 0]));
+    f1.add_term(
+        Rational::from(-2) / Rational::from(3),
+        Monomial::from(vec![0, 0]),
+    );
+
+    let mut f2 = Polynomial::from((2, monomial::MonomialOrder::Grlex));
+    f2.add_term(Rational::from(1), Monomial::from(vec![0, 2]));
+    f2.add_term(
+        Rational::from(-3) / Rational::from(5),
+        Monomial::from(vec![0, 0]),
+    );
+
+    let (gb, _) = groebner_basis::compute_groebner_basis_modular(vec![f0, f1, f2], true);
+
+    assert_eq!(
+        gb,
+        vec![Polynomial::from((
+            Rational::from(1),
+            2,
+            monomial::MonomialOrder::Grlex
+        ))]
+    );
+}
+
+#[test]
+fn test_groebner_basis_fraction_free() {
+    // x^2 + y^2 + z^2 - 1, x^2 + z^2 - y, x - z
+    let mut f0 = Polynomial::<Integer>::new(3, monomial::MonomialOrder::Lex);
+    f0.add_term(Integer::from(1), Monomial::from(vec![2, 0, 0]));
+    f0.add_term(Integer::from(1), Monomial::from(vec![0, 2, 0]));
+    f0.add_term(Integer::from(1), Monomial::from(vec![0, 0, 2]));
+    f0.add_term(Integer::from(-1), Monomial::from(vec![0, 0, 0]));
+
+    let mut f1 = Polynomial::<Integer>::new(3, monomial::MonomialOrder::Lex);
+    f1.add_term(Integer::from(1), Monomial::from(vec![2, 0, 0]));
+    f1.add_term(Integer::from(1), Monomial::from(vec![0, 0, 2]));
+    f1.add_term(Integer::from(-1), Monomial::from(vec![0, 1, 0]));
+
+    let mut f2 = Polynomial::<Integer>::new(3, monomial::MonomialOrder::Lex);
+    f2.add_term(Integer::from(1), Monomial::from(vec![1, 0, 0]));
+    f2.add_term(Integer::from(-1), Monomial::from(vec![0, 0, 1]));
+
+    let fs = vec![f0, f1, f2];
+
+    let gb = groebner_basis::compute_groebner_basis_fraction_free(fs.clone());
+
+    let fs_q = fs
+        .iter()
+        .map(|f| f.map_coeff(|c| Rational::from(c.clone())))
+        .collect::<Vec<_>>();
+    let gb_q = groebner_basis::compute_groebner_basis(fs_q)
+        .iter()
+        .map(|g| g.to_primitive_integer())
+        .collect::<Vec<_>>();
+
+    assert_eq!(gb, gb_q);
+
+    // 4z^4 + 2z^2 - 1
+    let mut correct_g2 = Polynomial::<Integer>::new(3, monomial::MonomialOrder::Lex);
+    correct_g2.add_term(Integer::from(4), Monomial::from(vec![0, 0, 4]));
+    correct_g2.add_term(Integer::from(2), Monomial::from(vec![0, 0, 2]));
+    correct_g2.add_term(Integer::from(-1), Monomial::from(vec![0, 0, 0]));
+
+    assert_eq!(gb[2], correct_g2);
+}
+
+#[allow(dead_code)]
+fn check_strong_groebner_basis_property(fs: &[Polynomial<Integer>], gs: &[Polynomial<Integer>]) {
+    let zero_polynomial = Polynomial::new(gs[0].get_n(), gs[0].get_monomial_order());
+    let gs_ = gs.iter().collect::<Vec<_>>();
+
+    for f in fs {
+        assert_eq!(f.strong_normal_form(&gs_), zero_polynomial);
+    }
+
+    for (i, gi) in gs.iter().enumerate() {
+        assert!(gi.fetch_lc().unwrap() > Integer::from(0));
+        for gj in gs.iter().skip(i + 1) {
+            let s = polynomial::s_polynomial_fraction_free(gi, gj).unwrap();
+            assert_eq!(s.strong_normal_form(&gs_), zero_polynomial);
+            let g = polynomial::g_polynomial(gi, gj).unwrap();
+            assert_eq!(g.strong_normal_form(&gs_), zero_polynomial);
+        }
+    }
+}
+
+#[test]
+fn test_strong_groebner_basis() {
+    // 2x, 3y
+    let mut f0 = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    f0.add_term(Integer::from(2), Monomial::from(vec![1, 0]));
+
+    let mut f1 = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    f1.add_term(Integer::from(3), Monomial::from(vec![0, 1]));
+
+    let fs = vec![f0.clone(), f1.clone()];
+    let gb = groebner_basis::compute_strong_groebner_basis(fs.clone());
+
+    check_strong_groebner_basis_property(&fs, &gb);
+
+    // xy = 3y * x - 2x * y も主項として必要
+    let mut xy = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    xy.add_term(Integer::from(1), Monomial::from(vec![1, 1]));
+
+    assert_eq!(gb, vec![xy, f0, f1]);
+}
+
+#[test]
+fn test_strong_groebner_basis_degenerate_prime() {
+    // x^2 + 1, 5x: 5 = x * 5x - 5(x^2 + 1) なので 5 で退化する
+    let mut f0 = Polynomial::<Integer>::new(1, monomial::MonomialOrder::Lex);
+    f0.add_term(Integer::from(1), Monomial::from(vec![2]));
+    f0.add_term(Integer::from(1), Monomial::from(vec![0]));
+
+    let mut f1 = Polynomial::<Integer>::new(1, monomial::MonomialOrder::Lex);
+    f1.add_term(Integer::from(5), Monomial::from(vec![1]));
+
+    let fs = vec![f0.clone(), f1];
+    let gb = groebner_basis::compute_strong_groebner_basis(fs.clone());
+
+    check_strong_groebner_basis_property(&fs, &gb);
+
+    let five = Polynomial::from((Integer::from(5), 1, monomial::MonomialOrder::Lex));
+    assert_eq!(gb, vec![f0, five]);
+}
+
+#[test]
+fn test_strong_groebner_basis_characteristic() {
+    // 3xy - 2y^2 + 4, 6x^2 - 4y, 2y^3 + 5
+    // Q 上では 1 を生成するが、Z 上では素数 43891 を法とした解が残る
+    let mut f0 = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Grlex);
+    f0.add_term(Integer::from(3), Monomial::from(vec![1, 1]));
+    f0.add_term(Integer::from(-2), Monomial::from(vec![0, 2]));
+    f0.add_term(Integer::from(4), Monomial::from(vec![0, 0]));
+
+    let mut f1 = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Grlex);
+    f1.add_term(Integer::from(6), Monomial::from(vec![2, 0]));
+    f1.add_term(Integer::from(-4), Monomial::from(vec![0, 1]));
+
+    let mut f2 = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Grlex);
+    f2.add_term(Integer::from(2), Monomial::from(vec![0, 3]));
+    f2.add_term(Integer::from(5), Monomial::from(vec![0, 0]));
+
+    let fs = vec![f0, f1, f2];
+    let gb = groebner_basis::compute_strong_groebner_basis(fs.clone());
+
+    check_strong_groebner_basis_property(&fs, &gb);
+
+    let mut g0 = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Grlex);
+    g0.add_term(Integer::from(1), Monomial::from(vec![1, 0]));
+    g0.add_term(Integer::from(14276), Monomial::from(vec![0, 0]));
+
+    let mut g1 = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Grlex);
+    g1.add_term(Integer::from(1), Monomial::from(vec![0, 1]));
+    g1.add_term(Integer::from(38442), Monomial::from(vec![0, 0]));
+
+    let g2 = Polynomial::from((Integer::from(43891), 2, monomial::MonomialOrder::Grlex));
+
+    assert_eq!(gb, vec![g0, g1, g2]);
+}
+
+#[test]
+fn test_groebner_basis_number_field() {
+    // Q(a), a^2 = 2
+    let field = NumberField::new(
+        vec![Rational::from(-2), Rational::zero(), Rational::one()],
+        "a",
+    );
+    let a = field.generator();
+
+    // x^2 - 2, (x - a)y - 1
+    let mut f0 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    f0.add_term(AlgebraicNumber::one(), Monomial::from(vec![2, 0]));
+    f0.add_term(AlgebraicNumber::from(-2), Monomial::from(vec![0, 0]));
+
+    let mut f1 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    f1.add_term(AlgebraicNumber::one(), Monomial::from(vec![1, 1]));
+    f1.add_term(-a.clone(), Monomial::from(vec![0, 1]));
+    f1.add_term(AlgebraicNumber::from(-1), Monomial::from(vec![0, 0]));
+
+    let gb = groebner_basis::compute_groebner_basis(vec![f0, f1]);
+
+    // x = -a, y = -1/(2a) = -a/4
+    let mut g0 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    g0.add_term(AlgebraicNumber::one(), Monomial::from(vec![1, 0]));
+    g0.add_term(a.clone(), Monomial::from(vec![0, 0]));
+
+    let mut g1 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    g1.add_term(AlgebraicNumber::one(), Monomial::from(vec![0, 1]));
+    g1.add_term(
+        field.element(vec![
+            Rational::zero(),
+            Rational::from(1) / Rational::from(4),
+        ]),
+        Monomial::from(vec![0, 0]),
+    );
+
+    assert_eq!(gb.len(), 2);
+    assert!(gb.contains(&g0));
+    assert!(gb.contains(&g1));
+    assert_eq!(g0.to_string(), "(x_1) + a Lex");
+}
+
+#[test]
+fn test_groebner_basis_rational_function() {
+    // Q(r)
+    let r = RationalFunction::parameter(0, 1, monomial::MonomialOrder::Lex);
+    let one = RationalFunction::one();
+
+    // x^2 + y^2 - r^2, r x - y
+    let mut f0 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    f0.add_term(one.clone(), Monomial::from(vec![2, 0]));
+    f0.add_term(one.clone(), Monomial::from(vec![0, 2]));
+    f0.add_term(-(r.clone() * r.clone()), Monomial::from(vec![0, 0]));
+
+    let mut f1 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    f1.add_term(r.clone(), Monomial::from(vec![1, 0]));
+    f1.add_term(-one.clone(), Monomial::from(vec![0, 1]));
+
+    let gb = groebner_basis::compute_groebner_basis(vec![f0, f1]);
+
+    // x - y / r, y^2 - r^4 / (r^2 + 1)
+    let mut g0 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    g0.add_term(one.clone(), Monomial::from(vec![1, 0]));
+    g0.add_term(-(one.clone() / r.clone()), Monomial::from(vec![0, 1]));
+
+    let r2 = r.clone() * r.clone();
+    let mut g1 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    g1.add_term(one.clone(), Monomial::from(vec![0, 2]));
+    g1.add_term(
+        -(r2.clone() * r2.clone() / (r2 + one)),
+        Monomial::from(vec![0, 0]),
+    );
+
+    assert_eq!(gb.len(), 2);
+    assert!(gb.contains(&g0));
+    assert!(gb.contains(&g1));
+}
+
+#[test]
+fn test_comprehensive_groebner_system() {
+    let term = |c: i64, v: Vec<i64>| {
+        Polynomial::from((
+            Rational::from(c),
+            Monomial::from(v),
+            monomial::MonomialOrder::Lex,
+        ))
+    };
+    let parameter = |c: i64, v: Vec<i64>| RationalFunction::from(term(c, v));
+    let one = RationalFunction::one();
+
+    // a x^2 + b x + 1 (変数 x, パラメータ a, b)
+    let f = &(&term(1, vec![2, 1, 0]) + &term(1, vec![1, 0, 1])) + &term(1, vec![0, 0, 0]);
+
+    let cells = groebner_basis::comprehensive_groebner_system(vec![f], 1);
+    assert_eq!(cells.len(), 3);
+
+    // a != 0
+    let mut g = Polynomial::new(1, monomial::MonomialOrder::Lex);
+    g.add_term(one.clone(), Monomial::from(vec![2]));
+    g.add_term(
+        parameter(1, vec![0, 1]) / parameter(1, vec![1, 0]),
+        Monomial::from(vec![1]),
+    );
+    g.add_term(
+        one.clone() / parameter(1, vec![1, 0]),
+        Monomial::from(vec![0]),
+    );
+    assert!(cells[0].equations.is_empty());
+    assert_eq!(cells[0].nonequations, vec![term(1, vec![1, 0])]);
+    assert_eq!(cells[0].basis, vec![g]);
+
+    // a = 0, b != 0
+    let mut g = Polynomial::new(1, monomial::MonomialOrder::Lex);
+    g.add_term(one.clone(), Monomial::from(vec![1]));
+    g.add_term(
+        one.clone() / parameter(1, vec![0, 1]),
+        Monomial::from(vec![0]),
+    );
+    assert_eq!(cells[1].equations, vec![term(1, vec![1, 0])]);
+    assert_eq!(cells[1].nonequations, vec![term(1, vec![0, 1])]);
+    assert_eq!(cells[1].basis, vec![g]);
+
+    // a = b = 0
+    assert_eq!(
+        cells[2].equations,
+        vec![term(1, vec![1, 0]), term(1, vec![0, 1])]
+    );
+    assert!(cells[2].nonequations.is_empty());
+    assert_eq!(
+        cells[2].basis,
+        vec![Polynomial::from((one, 1, monomial::MonomialOrder::Lex))]
+    );
+}
+
+#[test]
+#[should_panic(expected = "exceeds number of variables")]
+fn test_comprehensive_groebner_system_too_many_variables() {
+    let f = polynomial_from_terms(&[(1, &[1, 1]), (-1, &[0, 0])], monomial::MonomialOrder::Lex);
+    groebner_basis::comprehensive_groebner_system(vec![f], 3);
+}
+
+#[test]
+fn test_comprehensive_groebner_system_unit_cell() {
+    let term = |c: i64, v: Vec<i64>| {
+        Polynomial::from((
+            Rational::from(c),
+            Monomial::from(v),
+            monomial::MonomialOrder::Lex,
+        ))
+    };
+
+    // x^2 - a, x - b (変数 x, パラメータ a, b)
+    let fs = vec![
+        &term(1, vec![2, 0, 0]) - &term(1, vec![0, 1, 0]),
+        &term(1, vec![1, 0, 0]) - &term(1, vec![0, 0, 1]),
+    ];
+    let cells = groebner_basis::comprehensive_groebner_system(fs, 1);
+    assert_eq!(cells.len(), 2);
+
+    // a != b^2 では解が無い
+    let a_b2 = &term(1, vec![1, 0]) - &term(1, vec![0, 2]);
+    assert!(cells[0].equations.is_empty());
+    assert_eq!(cells[0].nonequations, vec![a_b2.clone()]);
+    assert_eq!(cells[0].basis.len(), 1);
+    assert!(cells[0].basis[0].fetch_lm().unwrap().fetch_total_degree() == Integer::from(0));
+
+    let mut g = Polynomial::new(1, monomial::MonomialOrder::Lex);
+    g.add_term(RationalFunction::one(), Monomial::from(vec![1]));
+    g.add_term(
+        -RationalFunction::from(term(1, vec![0, 1])),
+        Monomial::from(vec![0]),
+    );
+    assert_eq!(cells[1].equations, vec![a_b2]);
+    assert!(cells[1].nonequations.is_empty());
+    assert_eq!(cells[1].basis, vec![g]);
+}
+
+#[test]
+fn test_groebner_basis_finite_field() {
+    // GF(4) = GF(2)[t]/(t^2 + t + 1)
+    let field = FiniteField::<2>::conway(2, "t").unwrap();
+    let t = field.generator();
+
+    // x^2 + x + 1, (x + t) y + 1
+    let mut f0 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    f0.add_term(Fq::one(), Monomial::from(vec![2, 0]));
+    f0.add_term(Fq::one(), Monomial::from(vec![1, 0]));
+    f0.add_term(Fq::one(), Monomial::from(vec![0, 0]));
+
+    let mut f1 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    f1.add_term(Fq::one(), Monomial::from(vec![1, 1]));
+    f1.add_term(t.clone(), Monomial::from(vec![0, 1]));
+    f1.add_term(Fq::one(), Monomial::from(vec![0, 0]));
+
+    let gb = groebner_basis::compute_groebner_basis(vec![f0, f1]);
+
+    // x = t + 1, y = 1
+    let mut g0 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    g0.add_term(Fq::one(), Monomial::from(vec![1, 0]));
+    g0.add_term(t + Fq::one(), Monomial::from(vec![0, 0]));
+
+    let mut g1 = Polynomial::new(2, monomial::MonomialOrder::Lex);
+    g1.add_term(Fq::one(), Monomial::from(vec![0, 1]));
+    g1.add_term(Fq::one(), Monomial::from(vec![0, 0]));
+
+    assert_eq!(gb, vec![g0, g1]);
+}
+
+#[allow(dead_code)]
+fn polynomial_from_terms(
+    terms: &[(i64, &[i64])],
+    monomial_order: monomial::MonomialOrder,
+) -> Polynomial {
+    let mut f = Polynomial::new(terms[0].1.len(), monomial_order.clone());
+    for (c, m) in terms {
+        f.add_term(
+            Rational::from(*c),
+            Monomial::from((m.to_vec(), monomial_order.clone())),
+        );
+    }
+    f
+}
+
+#[allow(dead_code)]
+fn check_groebner_basis(
+    fs: &[&[(i64, &[i64])]],
+    expected: &[&[(i64, &[i64])]],
+    order: monomial::MonomialOrder,
+) {
+    let fs = fs
+        .iter()
+        .map(|f| polynomial_from_terms(f, order.clone()))
+        .collect::<Vec<_>>();
+
+    let gb = crate::groebner_basis::compute_groebner_basis(fs.clone());
+    check_reduced_groebner_basis_property(&fs, &gb);
+
+    assert_eq!(gb.len(), expected.len());
+    for g in expected {
+        let g = polynomial_from_terms(g, order.clone()).normalize();
+        assert!(gb.contains(&g), "{} is not in the basis", g);
+    }
+}
+
+#[test]
+fn test_groebner_basis_grevlex_cyclic4() {
+    // 値は sympy の groebner(order='grevlex') による
+    let fs: [&[(i64, &[i64])]; 4] = [
+        &[
+            (1, &[1, 0, 0, 0]),
+            (1, &[0, 1, 0, 0]),
+            (1, &[0, 0, 1, 0]),
+            (1, &[0, 0, 0, 1]),
+        ],
+        &[
+            (1, &[1, 1, 0, 0]),
+            (1, &[1, 0, 0, 1]),
+            (1, &[0, 1, 1, 0]),
+            (1, &[0, 0, 1, 1]),
+        ],
+        &[
+            (1, &[1, 1, 1, 0]),
+            (1, &[1, 1, 0, 1]),
+            (1, &[1, 0, 1, 1]),
+            (1, &[0, 1, 1, 1]),
+        ],
+        &[(1, &[1, 1, 1, 1]), (-1, &[0, 0, 0, 0])],
+    ];
+    let expected: [&[(i64, &[i64])]; 7] = [
+        &[
+            (1, &[0, 1, 1, 0]),
+            (-1, &[0, 1, 0, 1]),
+            (1, &[0, 0, 2, 4]),
+            (1, &[0, 0, 1, 1]),
+            (-2, &[0, 0, 0, 2]),
+        ],
+        &[
+            (1, &[0, 0, 3, 2]),
+            (1, &[0, 0, 2, 3]),
+            (-1, &[0, 0, 1, 0]),
+            (-1, &[0, 0, 0, 1]),
+        ],
+        &[
+            (1, &[0, 1, 0, 4]),
+            (-1, &[0, 1, 0, 0]),
+            (1, &[0, 0, 0, 5]),
+            (-1, &[0, 0, 0, 1]),
+        ],
+        &[
+            (1, &[0, 1, 1, 2]),
+            (-1, &[0, 1, 0, 3]),
+            (1, &[0, 0, 2, 2]),
+            (1, &[0, 0, 1, 3]),
+            (-1, &[0, 0, 0, 4]),
+            (-1, &[0, 0, 0, 0]),
+        ],
+        &[
+            (1, &[0, 1, 2, 0]),
+            (-1, &[0, 1, 0, 2]),
+            (1, &[0, 0, 2, 1]),
+            (-1, &[0, 0, 0, 3]),
+        ],
+        &[(1, &[0, 2, 0, 0]), (2, &[0, 1, 0, 1]), (1, &[0, 0, 0, 2])],
+        &[
+            (1, &[1, 0, 0, 0]),
+            (1, &[0, 1, 0, 0]),
+            (1, &[0, 0, 1, 0]),
+            (1, &[0, 0, 0, 1]),
+        ],
+    ];
+    check_groebner_basis(&fs, &expected, monomial::MonomialOrder::Grevlex);
+}
+
+#[test]
+fn test_groebner_basis_grevlex_katsura3() {
+    let fs: [&[(i64, &[i64])]; 4] = [
+        &[
+            (1, &[2, 0, 0, 0]),
+            (-1, &[1, 0, 0, 0]),
+            (2, &[0, 2, 0, 0]),
+            (2, &[0, 0, 2, 0]),
+            (2, &[0, 0, 0, 2]),
+        ],
+        &[
+            (2, &[1, 1, 0, 0]),
+            (2, &[0, 1, 1, 0]),
+            (-1, &[0, 1, 0, 0]),
+            (2, &[0, 0, 1, 1]),
+        ],
+        &[
+            (2, &[1, 0, 1, 0]),
+            (1, &[0, 2, 0, 0]),
+            (2, &[0, 1, 0, 1]),
+            (-1, &[0, 0, 1, 0]),
+        ],
+        &[
+            (1, &[1, 0, 0, 0]),
+            (2, &[0, 1, 0, 0]),
+            (2, &[0, 0, 1, 0]),
+            (2, &[0, 0, 0, 1]),
+            (-1, &[0, 0, 0, 0]),
+        ],
+    ];
+    let expected: [&[(i64, &[i64])]; 7] = [
+        &[
+            (1332, &[0, 1, 0, 1]),
+            (-39, &[0, 1, 0, 0]),
+            (3682, &[0, 0, 1, 1]),
+            (-389, &[0, 0, 1, 0]),
+            (32076, &[0, 0, 0, 4]),
+            (-13032, &[0, 0, 0, 3]),
+            (2472, &[0, 0, 0, 2]),
+            (-564, &[0, 0, 0, 1]),
+        ],
+        &[
+            (108, &[0, 1, 0, 2]),
+            (-12, &[0, 1, 0, 1]),
+            (-3, &[0, 1, 0, 0]),
+            (2, &[0, 0, 1, 1]),
+            (-4, &[0, 0, 1, 0]),
+            (-36, &[0, 0, 0, 3]),
+            (12, &[0, 0, 0, 2]),
+        ],
+        &[
+            (-9, &[0, 1, 0, 1]),
+            (3, &[0, 1, 0, 0]),
+            (162, &[0, 0, 1, 2]),
+            (-34, &[0, 0, 1, 1]),
+            (5, &[0, 0, 1, 0]),
+            (180, &[0, 0, 0, 3]),
+            (-78, &[0, 0, 0, 2]),
+            (6, &[0, 0, 0, 1]),
+        ],
+        &[
+            (7, &[0, 2, 0, 0]),
+            (14, &[0, 1, 0, 1]),
+            (-2, &[0, 1, 0, 0]),
+            (8, &[0, 0, 1, 1]),
+            (-1, &[0, 0, 1, 0]),
+            (12, &[0, 0, 0, 2]),
+            (-4, &[0, 0, 0, 1]),
+        ],
+        &[
+            (14, &[0, 1, 1, 0]),
+            (-28, &[0, 1, 0, 1]),
+            (1, &[0, 1, 0, 0]),
+            (-46, &[0, 0, 1, 1]),
+            (4, &[0, 0, 1, 0]),
+            (-48, &[0, 0, 0, 2]),
+            (16, &[0, 0, 0, 1]),
+        ],
+        &[
+            (14, &[0, 1, 0, 1]),
+            (-1, &[0, 1, 0, 0]),
+            (7, &[0, 0, 2, 0]),
+            (32, &[0, 0, 1, 1]),
+            (-4, &[0, 0, 1, 0]),
+            (27, &[0, 0, 0, 2]),
+            (-9, &[0, 0, 0, 1]),
+        ],
+        &[
+            (1, &[1, 0, 0, 0]),
+            (2, &[0, 1, 0, 0]),
+            (2, &[0, 0, 1, 0]),
+            (2, &[0, 0, 0, 1]),
+            (-1, &[0, 0, 0, 0]),
+        ],
+    ];
+    check_groebner_basis(&fs, &expected, monomial::MonomialOrder::Grevlex);
+
+    // 同じ順序を行列で与えても同じ基底になる
+    let matrix = monomial::MonomialOrder::matrix(vec![
+        vec![1, 1, 1, 1],
+        vec![0, 0, 0, -1],
+        vec![0, 0, -1, 0],
+        vec![0, -1, 0, 0],
+    ]);
+    check_groebner_basis(&fs, &expected, matrix);
+}
+
+#[test]
+fn test_eliminate() {
+    // x = t, y = t^2, z = t^3 から t を消去する (変数は x, y, t, z の順)
+    let order = monomial::MonomialOrder::Lex;
+    let fs: [&[(i64, &[i64])]; 3] = [
+        &[(1, &[1, 0, 0, 0]), (-1, &[0, 0, 1, 0])],
+        &[(1, &[0, 1, 0, 0]), (-1, &[0, 0, 2, 0])],
+        &[(1, &[0, 0, 0, 1]), (-1, &[0, 0, 3, 0])],
+    ];
+    let fs = fs
+        .iter()
+        .map(|f| polynomial_from_terms(f, order.clone()))
+        .collect::<Vec<_>>();
+
+    let gs = groebner_basis::eliminate(fs, &[2]);
+
+    // 捩れ3次曲線 (x, y, z の次数逆辞書式順序で降順)
+    let order = monomial::MonomialOrder::Grevlex;
+    let expected: [&[(i64, &[i64])]; 3] = [
+        &[(1, &[2, 0, 0]), (-1, &[0, 1, 0])],
+        &[(1, &[1, 1, 0]), (-1, &[0, 0, 1])],
+        &[(1, &[0, 2, 0]), (-1, &[1, 0, 1])],
+    ];
+    let expected = expected
+        .iter()
+        .map(|g| polynomial_from_terms(g, order.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(gs, expected);
+}
+
+// 全次数が大きい方が小さい (整列順序でない)
+#[allow(dead_code)]
+#[derive(Debug)]
+struct NegativeDegree;
+
+impl monomial::MonomialOrdering for NegativeDegree {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> std::cmp::Ordering {
+        let degree = |v: &[u32]| v.iter().sum::<u32>();
+        degree(rhs)
+            .cmp(&degree(lhs))
+            .then_with(|| monomial::Lex.cmp_exponents(lhs, rhs))
+    }
+
+    fn is_global(&self) -> bool {
+        false
+    }
+}
+
+#[test]
+#[should_panic(expected = "well-ordering")]
+fn test_groebner_basis_local_order() {
+    let order = monomial::MonomialOrder::custom(NegativeDegree);
+    let f = polynomial_from_terms(&[(1, &[1, 0]), (-1, &[0, 2])], order);
+    groebner_basis::compute_groebner_basis(vec![f]);
+}
@@ -0,0 +1,99 @@
+use crate::monomial::Monomial;
+use crate::polynomial::parser;
+use crate::polynomial::{ParseError, Polynomial, PolynomialHandlers, PolynomialRing};
+use crate::scalar::Rational;
+
+use std::sync::Arc;
+
+mod macaulay2;
+mod mathematica;
+mod singular;
+pub use macaulay2::{parse_macaulay2_output, to_macaulay2};
+pub use mathematica::{
+    parse_mathematica_groebner_basis, parse_mathematica_list, to_mathematica,
+    to_mathematica_groebner_basis, to_mathematica_list, GroebnerBasisCall,
+};
+pub use singular::{parse_singular_output, to_singular};
+
+pub(crate) fn variable_names(f: &Polynomial) -> Vec<String> {
+    match f.ring() {
+        Some(ring) => ring.names().to_vec(),
+        None => (1..=f.get_n()).map(|i| format!("x{}", i)).collect(),
+    }
+}
+
+fn monomial_to_input_form(m: &Monomial, names: &[String]) -> Vec<String> {
+    m.exponents()
+        .iter()
+        .zip(names.iter())
+        .filter(|(&e, _)| e != 0)
+        .map(|(&e, name)| match e {
+            1 => name.clone(),
+            _ => format!("{}^{}", name, e),
+        })
+        .collect()
+}
+
+// a1^2 - 2*a1*b1 + 3/4 の形 (Mathematica, Singular, Macaulay2 で共通に読める)
+//
+// 変数名は環の変数名、環を持たない多項式では x1, x2, ... とする。
+pub(crate) fn input_form(f: &Polynomial) -> String {
+    input_form_with_names(f, &variable_names(f))
+}
+
+// 変数名を names にした input_form
+pub(crate) fn input_form_with_names(f: &Polynomial, names: &[String]) -> String {
+    let mut output = String::new();
+
+    for (m, c) in f.terms().rev() {
+        let (is_negative, c) = if c < &Rational::zero() {
+            (true, -c.clone())
+        } else {
+            (false, c.clone())
+        };
+        match (output.is_empty(), is_negative) {
+            (true, true) => output.push('-'),
+            (true, false) => {}
+            (false, true) => output.push_str(" - "),
+            (false, false) => output.push_str(" + "),
+        }
+
+        let mut factors = monomial_to_input_form(m, names);
+        if factors.is_empty() || c != Rational::one() {
+            factors.insert(0, c.to_string());
+        }
+        output.push_str(&factors.join("*"));
+    }
+
+    if output.is_empty() {
+        output.push('0');
+    }
+    output
+}
+
+// 改行かカンマで区切られた多項式を読む
+//
+// 各片は strip で前後の余計な部分 (g[1]= や括弧など) を取り除いてから読み、空の片は無視する。
+pub(crate) fn parse_separated(
+    ring: &Arc<PolynomialRing>,
+    s: &str,
+    strip: impl Fn(&str) -> &str,
+) -> Result<Vec<Polynomial>, ParseError> {
+    let mut ret = Vec::new();
+    let mut start = 0;
+    for (i, c) in s.char_indices().chain(std::iter::once((s.len(), '\n'))) {
+        if c != ',' && c != '\n' {
+            continue;
+        }
+        let piece = strip(&s[start..i]).trim();
+        if !piece.is_empty() {
+            // piece は s の部分文字列
+            let offset = piece.as_ptr() as usize - s.as_ptr() as usize;
+            ret.push(parser::parse_at(ring, piece, offset)?);
+        }
+        start = i + c.len_utf8();
+    }
+    Ok(ret)
+}
+
+mod test;
@@ -0,0 +1,82 @@
+use crate::interop::{input_form_with_names, parse_separated};
+use crate::monomial::MonomialOrder;
+use crate::polynomial::{ParseError, Polynomial, PolynomialHandlers, PolynomialRing};
+
+use std::sync::Arc;
+
+// offset 個の 0 を前に付けた重み
+fn weights<T: ToString>(offset: usize, w: &[T]) -> String {
+    let w = std::iter::repeat_n("0".to_string(), offset)
+        .chain(w.iter().map(T::to_string))
+        .collect::<Vec<_>>();
+    format!("Weights => {{{}}}", w.join(", "))
+}
+
+// MonomialOrder => {...} の要素 (offset 番目の変数から size 個の変数の順序)
+fn components(order: &MonomialOrder, offset: usize, size: usize) -> Vec<String> {
+    match order {
+        MonomialOrder::Lex => vec![format!("Lex => {}", size)],
+        MonomialOrder::Grlex => vec![format!("GLex => {}", size)],
+        MonomialOrder::Grevlex => vec![format!("GRevLex => {}", size)],
+        MonomialOrder::Weighted(w) => {
+            let mut ret = vec![weights(offset, w.weights())];
+            ret.extend(components(w.tie_breaker(), offset, size));
+            ret
+        }
+        // 行列順序は全順序なので後ろに付く GRevLex は効かない
+        MonomialOrder::Matrix(m) => m.rows().iter().map(|row| weights(offset, row)).collect(),
+        MonomialOrder::Block(b) => {
+            let mut ret = Vec::new();
+            let mut offset = offset;
+            for (size, order) in b.blocks() {
+                ret.extend(components(order, offset, *size));
+                offset += size;
+            }
+            ret
+        }
+        MonomialOrder::Custom(_) => {
+            panic!("monomial order not supported by Macaulay2: {}", order)
+        }
+    }
+}
+
+/// ring 上の fs のグレブナー基底を計算して表示する Macaulay2 のスクリプト
+///
+/// QQ 上の多項式環の宣言、入力のイデアル、gb の呼び出しからなる。結果は {...} の形で表示され、
+/// parse_macaulay2_output で読める。
+pub fn to_macaulay2(ring: &Arc<PolynomialRing>, fs: &[Polynomial]) -> String {
+    let order = ring.get_monomial_order();
+    assert!(fs
+        .iter()
+        .all(|g| g.get_n() == ring.get_n() && g.get_monomial_order() == order));
+
+    let ideal = if fs.is_empty() {
+        // 空の ideal() は環が決まらない
+        "I = ideal(0_R);".to_string()
+    } else {
+        let ideal = fs
+            .iter()
+            .map(|f| input_form_with_names(f, ring.names()))
+            .collect::<Vec<_>>();
+        format!("I = ideal(\n  {});", ideal.join(",\n  "))
+    };
+    format!(
+        "R = QQ[{}, MonomialOrder => {{{}}}];\n\
+         {}\n\
+         G = gb I;\n\
+         print toString flatten entries gens G;\n",
+        ring.names().join(", "),
+        components(&order, 0, ring.get_n()).join(", "),
+        ideal
+    )
+}
+
+/// Macaulay2 が表示した多項式のリスト ({x^2-y*z, ...} やカンマ区切り) を ring の多項式として読む
+pub fn parse_macaulay2_output(
+    ring: &Arc<PolynomialRing>,
+    s: &str,
+) -> Result<Vec<Polynomial>, ParseError> {
+    parse_separated(ring, s, |piece| {
+        piece.trim().trim_start_matches('{').trim_end_matches('}')
+    })
+}
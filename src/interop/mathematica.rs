@@ -0,0 +1,250 @@
+use crate::interop::{input_form, variable_names};
+use crate::monomial::{MatrixOrder, MonomialOrder};
+use crate::polynomial::parser::{Parser, Token};
+use crate::polynomial::{ParseError, Polynomial, PolynomialHandlers, PolynomialRing};
+use crate::scalar::Integer;
+
+use std::sync::Arc;
+
+/// Mathematica の GroebnerBasis[{f1, ...}, {x1, ...}] の引数
+#[derive(Clone, Debug)]
+pub struct GroebnerBasisCall {
+    pub ring: Arc<PolynomialRing>,
+    pub polynomials: Vec<Polynomial>,
+}
+
+// {f1, f2, ...}
+fn list(parser: &mut Parser) -> Result<Vec<Polynomial>, ParseError> {
+    let mut ret = Vec::new();
+    parser.expect(Token::LBrace)?;
+    if parser.peek() == Some(Token::RBrace) {
+        parser.cursor += 1;
+        return Ok(ret);
+    }
+    loop {
+        ret.push(parser.expr()?);
+        match parser.peek() {
+            Some(Token::Comma) => parser.cursor += 1,
+            Some(Token::RBrace) => {
+                parser.cursor += 1;
+                return Ok(ret);
+            }
+            _ => return Err(parser.unexpected()),
+        }
+    }
+}
+
+// 対応する閉じ括弧まで読み飛ばす
+fn skip_group(parser: &mut Parser) -> Result<(), ParseError> {
+    let mut depth = 0usize;
+    loop {
+        match parser.peek() {
+            Some(Token::LBrace) | Some(Token::LBracket) | Some(Token::LParen) => depth += 1,
+            Some(Token::RBrace) | Some(Token::RBracket) | Some(Token::RParen) => depth -= 1,
+            Some(_) => {}
+            None => return Err(parser.unexpected()),
+        }
+        parser.cursor += 1;
+        if depth == 0 {
+            return Ok(());
+        }
+    }
+}
+
+fn ident<'a>(parser: &mut Parser<'a>) -> Result<&'a str, ParseError> {
+    if parser.peek() != Some(Token::Ident) {
+        return Err(parser.unexpected());
+    }
+    let name = parser.text();
+    parser.cursor += 1;
+    Ok(name)
+}
+
+// {a1, a2, ...} (重複は許さない)
+fn variables<'a>(parser: &mut Parser<'a>) -> Result<Vec<&'a str>, ParseError> {
+    let mut ret = Vec::new();
+    parser.expect(Token::LBrace)?;
+    loop {
+        let position = parser.position();
+        let name = ident(parser)?;
+        if ret.contains(&name) {
+            return Err(ParseError::UnexpectedToken(position, name.to_string()));
+        }
+        ret.push(name);
+        match parser.peek() {
+            Some(Token::Comma) => parser.cursor += 1,
+            _ => break,
+        }
+    }
+    parser.expect(Token::RBrace)?;
+    Ok(ret)
+}
+
+fn integer(parser: &mut Parser) -> Result<i64, ParseError> {
+    let negative = parser.peek() == Some(Token::Minus);
+    if negative {
+        parser.cursor += 1;
+    }
+    if parser.peek() != Some(Token::Number) {
+        return Err(parser.unexpected());
+    }
+    let a = parser
+        .text()
+        .parse::<i64>()
+        .map_err(|_| parser.unexpected())?;
+    parser.cursor += 1;
+    Ok(if negative { -a } else { a })
+}
+
+// MonomialOrder -> ... の右辺 (n は変数の数)
+fn monomial_order(parser: &mut Parser, n: usize) -> Result<MonomialOrder, ParseError> {
+    if parser.peek() == Some(Token::LBrace) {
+        // 重み行列
+        let start = parser.position();
+        let mut rows = Vec::new();
+        parser.expect(Token::LBrace)?;
+        loop {
+            let mut row = Vec::new();
+            let position = parser.position();
+            parser.expect(Token::LBrace)?;
+            loop {
+                row.push(integer(parser)?);
+                match parser.peek() {
+                    Some(Token::Comma) => parser.cursor += 1,
+                    _ => break,
+                }
+            }
+            if row.len() != n {
+                return Err(ParseError::UnexpectedToken(position, "{".to_string()));
+            }
+            parser.expect(Token::RBrace)?;
+            rows.push(row);
+            match parser.peek() {
+                Some(Token::Comma) => parser.cursor += 1,
+                _ => break,
+            }
+        }
+        parser.expect(Token::RBrace)?;
+        let matrix =
+            MatrixOrder::try_new(rows).map_err(|e| ParseError::InvalidMonomialOrder(start, e))?;
+        return Ok(MonomialOrder::Matrix(Arc::new(matrix)));
+    }
+
+    let position = parser.position();
+    match ident(parser)? {
+        "Lexicographic" => Ok(MonomialOrder::Lex),
+        "DegreeLexicographic" => Ok(MonomialOrder::Grlex),
+        "DegreeReverseLexicographic" => Ok(MonomialOrder::Grevlex),
+        name => Err(ParseError::UnexpectedToken(position, name.to_string())),
+    }
+}
+
+/// Mathematica の多項式のリスト {f1, f2, ...} を ring の多項式として読む
+///
+/// 空白を挟んで並べただけの積 (2 a1 b1) も使える。
+pub fn parse_mathematica_list(
+    ring: &Arc<PolynomialRing>,
+    s: &str,
+) -> Result<Vec<Polynomial>, ParseError> {
+    let mut parser = Parser::new(s)?;
+    parser.set_ring(ring);
+    let ret = list(&mut parser)?;
+    parser.finish()?;
+    Ok(ret)
+}
+
+/// GroebnerBasis[{f1, ...}, {x1, ...}] を読む
+///
+/// 変数のリストから多項式環を作る。単項式順序は Mathematica と同じく既定が辞書式順序で、
+/// MonomialOrder -> DegreeLexicographic などのオプションや重み行列も指定できる
+/// (重み行列が単項式順序にならない時は ParseError::InvalidMonomialOrder を返す)。
+pub fn parse_mathematica_groebner_basis(s: &str) -> Result<GroebnerBasisCall, ParseError> {
+    let mut parser = Parser::new(s)?;
+
+    let position = parser.position();
+    match ident(&mut parser)? {
+        "GroebnerBasis" => {}
+        name => return Err(ParseError::UnexpectedToken(position, name.to_string())),
+    }
+    parser.expect(Token::LBracket)?;
+
+    // 変数が分かってから多項式を読む
+    let start = parser.cursor;
+    if parser.peek() != Some(Token::LBrace) {
+        return Err(parser.unexpected());
+    }
+    skip_group(&mut parser)?;
+    parser.expect(Token::Comma)?;
+    let names = variables(&mut parser)?;
+
+    let mut order = MonomialOrder::Lex;
+    if parser.peek() == Some(Token::Comma) {
+        parser.cursor += 1;
+        let position = parser.position();
+        match ident(&mut parser)? {
+            "MonomialOrder" => {}
+            name => return Err(ParseError::UnexpectedToken(position, name.to_string())),
+        }
+        parser.expect(Token::Arrow)?;
+        order = monomial_order(&mut parser, names.len())?;
+    }
+    parser.expect(Token::RBracket)?;
+    parser.finish()?;
+
+    let ring = PolynomialRing::new(&names, order);
+    parser.set_ring(&ring);
+    parser.cursor = start;
+    let polynomials = list(&mut parser)?;
+
+    Ok(GroebnerBasisCall { ring, polynomials })
+}
+
+/// Mathematica の入力形式 (a1^2 - 2*a1*b1 + 3/4 など)
+///
+/// 変数名は環の変数名、環を持たない多項式では x1, x2, ... とする。
+pub fn to_mathematica(f: &Polynomial) -> String {
+    input_form(f)
+}
+
+/// {f1, f2, ...}
+pub fn to_mathematica_list(fs: &[Polynomial]) -> String {
+    let fs = fs.iter().map(to_mathematica).collect::<Vec<_>>();
+    format!("{{{}}}", fs.join(", "))
+}
+
+fn monomial_order_to_mathematica(order: &MonomialOrder, n: usize) -> String {
+    match order {
+        MonomialOrder::Lex => "Lexicographic".to_string(),
+        MonomialOrder::Grlex => "DegreeLexicographic".to_string(),
+        MonomialOrder::Grevlex => "DegreeReverseLexicographic".to_string(),
+        // 残りは同じ順序を与える正方行列にする
+        _ => {
+            let rows = order
+                .to_matrix(n)
+                .unwrap_or_else(|| panic!("monomial order not supported by Mathematica: {}", order))
+                .iter()
+                .map(|row| {
+                    let row = row.iter().map(Integer::to_string).collect::<Vec<_>>();
+                    format!("{{{}}}", row.join(", "))
+                })
+                .collect::<Vec<_>>();
+            format!("{{{}}}", rows.join(", "))
+        }
+    }
+}
+
+/// GroebnerBasis[{f1, ...}, {x1, ...}, MonomialOrder -> ...]
+///
+/// 単項式順序は辞書式、次数付き辞書式、次数逆辞書式以外は行列で渡す (Custom は対応しない)。
+pub fn to_mathematica_groebner_basis(fs: &[Polynomial]) -> String {
+    let (names, order) = match fs.first() {
+        Some(f) => (variable_names(f), f.get_monomial_order()),
+        None => (Vec::new(), MonomialOrder::Lex),
+    };
+    format!(
+        "GroebnerBasis[{}, {{{}}}, MonomialOrder -> {}]",
+        to_mathematica_list(fs),
+        names.join(", "),
+        monomial_order_to_mathematica(&order, names.len())
+    )
+}
@@ -0,0 +1,86 @@
+use crate::interop::{input_form_with_names, parse_separated};
+use crate::monomial::MonomialOrder;
+use crate::polynomial::{ParseError, Polynomial, PolynomialHandlers, PolynomialRing};
+
+use std::sync::Arc;
+
+fn join<T: ToString>(v: &[T]) -> String {
+    v.iter().map(T::to_string).collect::<Vec<_>>().join(",")
+}
+
+// Singular の順序の並び (n は変数の数)
+fn orderings(order: &MonomialOrder, n: usize) -> Vec<String> {
+    match order {
+        MonomialOrder::Lex => vec![format!("lp({})", n)],
+        MonomialOrder::Grlex => vec![format!("Dp({})", n)],
+        MonomialOrder::Grevlex => vec![format!("dp({})", n)],
+        MonomialOrder::Weighted(w) => {
+            // wp, Wp は重み付き次数の次に全次数を比べないので a(w) と元の順序を並べる
+            let mut ret = vec![format!("a({})", join(w.weights()))];
+            ret.extend(orderings(w.tie_breaker(), n));
+            ret
+        }
+        MonomialOrder::Matrix(m) if m.rows().len() == n => {
+            vec![format!("M({})", join(&m.rows().concat()))]
+        }
+        // M は正方行列しか受け付けないので各行を a で並べ、最後に全順序を付ける
+        MonomialOrder::Matrix(m) => m
+            .rows()
+            .iter()
+            .map(|row| format!("a({})", join(row)))
+            .chain(std::iter::once(format!("lp({})", n)))
+            .collect(),
+        MonomialOrder::Block(b) => b
+            .blocks()
+            .iter()
+            .flat_map(|(size, order)| orderings(order, *size))
+            .collect(),
+        MonomialOrder::Custom(_) => panic!("monomial order not supported by Singular: {}", order),
+    }
+}
+
+/// ring 上の fs の簡約グレブナー基底を計算して表示する Singular のスクリプト
+///
+/// 標数0の ring 宣言、入力のイデアル、std の呼び出しからなる。結果は g[1]=... の形で表示され、
+/// parse_singular_output で読める。
+pub fn to_singular(ring: &Arc<PolynomialRing>, fs: &[Polynomial]) -> String {
+    let order = ring.get_monomial_order();
+    assert!(fs
+        .iter()
+        .all(|g| g.get_n() == ring.get_n() && g.get_monomial_order() == order));
+
+    let ideal = if fs.is_empty() {
+        "ideal i = 0;".to_string()
+    } else {
+        let ideal = fs
+            .iter()
+            .map(|f| input_form_with_names(f, ring.names()))
+            .collect::<Vec<_>>();
+        format!("ideal i =\n  {};", ideal.join(",\n  "))
+    };
+    format!(
+        "ring r = 0, ({}), ({});\n\
+         short = 0;\n\
+         {}\n\
+         option(redSB);\n\
+         ideal g = std(i);\n\
+         g;\n\
+         quit;\n",
+        ring.names().join(", "),
+        orderings(&order, ring.get_n()).join(", "),
+        ideal
+    )
+}
+
+/// Singular が表示した多項式の並び (g[1]=... の行やカンマ区切り) を ring の多項式として読む
+///
+/// Singular の std は係数を整数にした多項式を返すので、比べる時は主係数で割ること。
+pub fn parse_singular_output(
+    ring: &Arc<PolynomialRing>,
+    s: &str,
+) -> Result<Vec<Polynomial>, ParseError> {
+    parse_separated(ring, s, |piece| match piece.find('=') {
+        Some(i) if piece[..i].trim_end().ends_with(']') => &piece[i + 1..],
+        _ => piece,
+    })
+}
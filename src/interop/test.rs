@@ -0,0 +1,263 @@
+#[cfg(test)]
+#[allow(unused_imports)]
+use super::{
+    parse_macaulay2_output, parse_mathematica_groebner_basis, parse_mathematica_list,
+    parse_singular_output, to_macaulay2, to_mathematica, to_mathematica_groebner_basis,
+    to_mathematica_list, to_singular,
+};
+#[allow(unused_imports)]
+use crate::groebner_basis::compute_groebner_basis;
+#[allow(unused_imports)]
+use crate::monomial::{MatrixOrderError, MonomialOrder, MonomialOrdering};
+#[allow(unused_imports)]
+use crate::polynomial::{ParseError, Polynomial, PolynomialHandlers, PolynomialRing};
+
+#[test]
+fn test_mathematica() {
+    // tokyo-tech-2020-2-1.txt の f3 と 1 - y g1 の展開形
+    let call = parse_mathematica_groebner_basis(
+        "GroebnerBasis[{-1 + (-(a2 - b2) (-b1 + c1) + (a1 - b1) (-b2 + c2)) v, 1 - y (a1^2 - 2 a1 b1)}, \
+         {a1, a2, b1, b2, c1, c2, v, y}]",
+    )
+    .unwrap();
+    assert_eq!(
+        call.ring.names(),
+        ["a1", "a2", "b1", "b2", "c1", "c2", "v", "y"]
+    );
+    assert_eq!(call.ring.get_monomial_order(), MonomialOrder::Lex);
+    let ring = &call.ring;
+    assert_eq!(
+        call.polynomials,
+        vec![
+            ring.parse("-1 + a2 b1 v - a1 b2 v - a2 c1 v + b2 c1 v + a1 c2 v - b1 c2 v")
+                .unwrap(),
+            ring.parse("1 - a1^2 y + 2 a1 b1 y").unwrap(),
+        ]
+    );
+
+    let f = ring.parse("a1^2 - 2 a1 b1 - 3/4 c1 + 1/2").unwrap();
+    assert_eq!(to_mathematica(&f), "a1^2 - 2*a1*b1 - 3/4*c1 + 1/2");
+    assert_eq!(
+        to_mathematica(&ring.parse("-a1 (y - 1)").unwrap()),
+        "-a1*y + a1"
+    );
+    assert_eq!(to_mathematica(&ring.parse("0").unwrap()), "0");
+
+    // 書き出したものを読み戻すと同じ多項式になる
+    let ring = PolynomialRing::new(&["x", "y", "z"], MonomialOrder::Grevlex);
+    let fs = parse_mathematica_list(&ring, "{x^2 - y z, x y - 2/3 z^3, 7}").unwrap();
+    let gs = compute_groebner_basis(fs.clone());
+    let s = to_mathematica_groebner_basis(&fs);
+    assert_eq!(
+        s,
+        "GroebnerBasis[{x^2 - y*z, -2/3*z^3 + x*y, 7}, {x, y, z}, \
+         MonomialOrder -> DegreeReverseLexicographic]"
+    );
+    let call = parse_mathematica_groebner_basis(&s).unwrap();
+    assert_eq!(call.ring.as_ref(), ring.as_ref());
+    assert_eq!(call.polynomials, fs);
+    assert_eq!(
+        parse_mathematica_list(&ring, &to_mathematica_list(&gs)).unwrap(),
+        gs
+    );
+
+    let matrix = "GroebnerBasis[{x - y}, {x, y}, MonomialOrder -> {{1, 1}, {0, -1}}]";
+    let call = parse_mathematica_groebner_basis(matrix).unwrap();
+    assert_eq!(
+        call.ring.get_monomial_order(),
+        MonomialOrder::matrix(vec![vec![1, 1], vec![0, -1]])
+    );
+    assert_eq!(to_mathematica_groebner_basis(&call.polynomials), matrix);
+
+    // 重み付き次数順序とブロック順序は同じ順序を与える行列にする
+    for (order, expected) in [
+        (
+            MonomialOrder::weighted(vec![1, 2, 3], MonomialOrder::Grevlex),
+            "{{1, 2, 3}, {1, 1, 1}, {1, 1, 0}}",
+        ),
+        (
+            MonomialOrder::block(vec![(1, MonomialOrder::Lex), (2, MonomialOrder::Grlex)]),
+            "{{1, 0, 0}, {0, 1, 1}, {0, 1, 0}}",
+        ),
+    ] {
+        let ring = PolynomialRing::new(&["x", "y", "z"], order.clone());
+        let s = to_mathematica_groebner_basis(&[ring.parse("x + y + z").unwrap()]);
+        assert!(
+            s.ends_with(&format!("MonomialOrder -> {}]", expected)),
+            "{}",
+            s
+        );
+        let matrix = parse_mathematica_groebner_basis(&s)
+            .unwrap()
+            .ring
+            .get_monomial_order();
+        let alphas = (0..27)
+            .map(|i| vec![i % 3, i / 3 % 3, i / 9])
+            .collect::<Vec<Vec<u32>>>();
+        for a in &alphas {
+            for b in &alphas {
+                assert_eq!(order.cmp_exponents(a, b), matrix.cmp_exponents(a, b));
+            }
+        }
+    }
+
+    assert_eq!(
+        parse_mathematica_groebner_basis("GroebnerBasis[{x + w}, {x, y}]").unwrap_err(),
+        ParseError::UnknownVariable(19, "w".to_string())
+    );
+    assert_eq!(
+        parse_mathematica_groebner_basis("GroebnerBasis[{x}, {x, x}]").unwrap_err(),
+        ParseError::UnexpectedToken(23, "x".to_string())
+    );
+    assert_eq!(
+        parse_mathematica_groebner_basis("GroebnerBasis[{x}, {x}, MonomialOrder -> Foo]")
+            .unwrap_err(),
+        ParseError::UnexpectedToken(41, "Foo".to_string())
+    );
+    // 単項式順序にならない重み行列は panic せずエラーにする
+    assert_eq!(
+        parse_mathematica_groebner_basis(
+            "GroebnerBasis[{x}, {x, y}, MonomialOrder -> {{1, -1}, {0, 1}}]"
+        )
+        .unwrap_err(),
+        ParseError::InvalidMonomialOrder(44, MatrixOrderError::ColumnSign(1))
+    );
+    assert_eq!(
+        parse_mathematica_groebner_basis("GroebnerBasis[{x}, {x, y}, MonomialOrder -> {{1, 1}}]")
+            .unwrap_err(),
+        ParseError::InvalidMonomialOrder(44, MatrixOrderError::Rank)
+    );
+    assert_eq!(
+        parse_mathematica_list(&ring, "{x, y").unwrap_err(),
+        ParseError::UnexpectedEnd(5)
+    );
+}
+
+// 手元にあれば外部のプログラムでスクリプトを実行した出力
+#[allow(dead_code)]
+fn run(program: &str, args: &[&str], script: &str) -> Option<String> {
+    let path =
+        std::env::temp_dir().join(format!("groebner_basis_{}_{}", program, std::process::id()));
+    std::fs::write(&path, script).ok()?;
+    let output = std::process::Command::new(program)
+        .args(args)
+        .arg(&path)
+        .output();
+    let _ = std::fs::remove_file(&path);
+    let output = output.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+// 主係数を1にして主項の降順に並べる
+#[allow(dead_code)]
+fn monic_sorted(fs: Vec<Polynomial>) -> Vec<Polynomial> {
+    let mut fs = fs.into_iter().map(|f| f.normalize()).collect::<Vec<_>>();
+    fs.sort_by_key(|f| std::cmp::Reverse(f.fetch_lm()));
+    fs
+}
+
+#[test]
+fn test_singular() {
+    let order = MonomialOrder::block(vec![(2, MonomialOrder::Grevlex), (1, MonomialOrder::Lex)]);
+    let ring = PolynomialRing::new(&["x", "y", "t"], order);
+    let fs = parse_mathematica_list(&ring, "{x - t^2, y - t^3}").unwrap();
+    assert_eq!(
+        to_singular(&ring, &fs),
+        "ring r = 0, (x, y, t), (dp(2), lp(1));\n\
+         short = 0;\n\
+         ideal i =\n  x - t^2,\n  y - t^3;\n\
+         option(redSB);\n\
+         ideal g = std(i);\n\
+         g;\n\
+         quit;\n"
+    );
+
+    let orders = [
+        (
+            MonomialOrder::weighted(vec![1, 2, 0], MonomialOrder::Lex),
+            "(a(1,2,0), lp(3))",
+        ),
+        (
+            MonomialOrder::matrix(vec![vec![1, 1, 1], vec![0, 0, -1], vec![0, -1, 0]]),
+            "(M(1,1,1,0,0,-1,0,-1,0))",
+        ),
+        (
+            MonomialOrder::matrix(vec![
+                vec![1, 1, 1],
+                vec![0, 0, -1],
+                vec![0, -1, 0],
+                vec![1, 0, 0],
+            ]),
+            "(a(1,1,1), a(0,0,-1), a(0,-1,0), a(1,0,0), lp(3))",
+        ),
+    ];
+    for (order, expected) in orders.iter() {
+        let ring = PolynomialRing::new(&["x", "y", "z"], order.clone());
+        let script = to_singular(&ring, &[ring.parse("x + y z").unwrap()]);
+        assert!(script.starts_with(&format!("ring r = 0, (x, y, z), {};", expected)));
+    }
+
+    // 空のイデアル
+    assert!(to_singular(&ring, &[]).contains("\nideal i = 0;\n"));
+
+    let gs = compute_groebner_basis(fs.clone());
+    let output = "g[1]=y-t^3\ng[2]=x-t^2\n";
+    let hs = parse_singular_output(&ring, output).unwrap();
+    assert_eq!(hs, parse_singular_output(&ring, "y-t^3,x-t^2").unwrap());
+    assert_eq!(monic_sorted(hs), gs);
+    assert_eq!(
+        parse_singular_output(&ring, "g[1]=x^2\ng[2]=x+w\n").unwrap_err(),
+        ParseError::UnknownVariable(16, "w".to_string())
+    );
+
+    if let Some(output) = run("Singular", &["-q"], &to_singular(&ring, &fs)) {
+        let hs = parse_singular_output(&ring, &output).unwrap();
+        assert_eq!(monic_sorted(hs), gs);
+    }
+}
+
+#[test]
+fn test_macaulay2() {
+    let order = MonomialOrder::block(vec![
+        (1, MonomialOrder::weighted(vec![2], MonomialOrder::Lex)),
+        (2, MonomialOrder::Grevlex),
+    ]);
+    let ring = PolynomialRing::new(&["t", "x", "y"], order);
+    let fs = parse_mathematica_list(&ring, "{x - t^2, y - t^3}").unwrap();
+    assert_eq!(
+        to_macaulay2(&ring, &fs),
+        "R = QQ[t, x, y, MonomialOrder => {Weights => {2}, Lex => 1, GRevLex => 2}];\n\
+         I = ideal(\n  -t^2 + x,\n  -t^3 + y);\n\
+         G = gb I;\n\
+         print toString flatten entries gens G;\n"
+    );
+
+    let ring2 = PolynomialRing::new(
+        &["x", "y"],
+        MonomialOrder::matrix(vec![vec![1, 1], vec![0, -1]]),
+    );
+    assert!(to_macaulay2(&ring2, &[ring2.parse("x").unwrap()])
+        .starts_with("R = QQ[x, y, MonomialOrder => {Weights => {1, 1}, Weights => {0, -1}}];"));
+
+    assert!(to_macaulay2(&ring, &[]).contains("\nI = ideal(0_R);\n"));
+
+    let gs = compute_groebner_basis(fs.clone());
+    let hs = parse_macaulay2_output(&ring, "{y^2-x^3, t*y-x^2, t*x-y, t^2-x}\n").unwrap();
+    assert_eq!(monic_sorted(hs), gs);
+    assert_eq!(
+        parse_macaulay2_output(&ring, "{(2/3)*x, -(1/2)*y}").unwrap(),
+        parse_mathematica_list(&ring, "{2/3 x, -1/2 y}").unwrap()
+    );
+    assert_eq!(
+        parse_macaulay2_output(&ring, "{x, y^}").unwrap_err(),
+        ParseError::InvalidExponent(6)
+    );
+
+    if let Some(output) = run("M2", &["--script"], &to_macaulay2(&ring, &fs)) {
+        let hs = parse_macaulay2_output(&ring, &output).unwrap();
+        assert_eq!(monic_sorted(hs), gs);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod degree;
 pub mod groebner_basis;
+pub mod interop;
 pub mod monomial;
 pub mod polynomial;
 pub mod scalar;
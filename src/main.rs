@@ -1,13 +1,9 @@
-mod degree;
-mod groebner_basis;
-mod monomial;
-mod polynomial;
-mod scalar;
+extern crate groebner_basis;
 
-use degree::Zn;
-use monomial::Monomial;
-use polynomial::{Polynomial, PolynomialHandlers};
-use scalar::{Integer, Rational};
+use groebner_basis::degree::Zn;
+use groebner_basis::monomial::{self, Monomial};
+use groebner_basis::polynomial::{self, Polynomial, PolynomialHandlers};
+use groebner_basis::scalar::{Integer, Rational};
 use std::time::Instant;
 
 fn factorial(n: &Integer) -> Integer {
@@ -25,7 +21,7 @@ fn main() {
     }
     let end = start.elapsed();
 
-    println!("{}.{:03}sec", end.as_secs(), end.subsec_nanos() / 1_000_000);
+    println!("{}.{:03}sec", end.as_secs(), end.subsec_millis());
 
     let z0 = Zn::from(vec![Integer::from(0), Integer::from(1), Integer::from(2)]);
     let z1 = Zn::from(vec![Integer::from(3), Integer::from(4), Integer::from(5)]);
@@ -78,7 +74,7 @@ fn main() {
         Monomial::from(vec![Integer::from(0), Integer::from(0)]),
     );
 
-    let (a, r) = f.polynomial_divide(&vec![f1, f2]);
+    let (a, r) = f.polynomial_divide(&[f1, f2]);
 
     println!("fin");
     for p in a {
@@ -171,7 +167,7 @@ fn main() {
         println!("{}", f);
     }
 
-    let gb = groebner_basis::compute_groebner_basis(fs);
+    let gb = groebner_basis::groebner_basis::compute_groebner_basis(fs);
 
     println!("fin");
     for g in gb {
@@ -302,11 +298,11 @@ fn main() {
     }
 
     let start = Instant::now();
-    let gb = groebner_basis::compute_groebner_basis(fs);
+    let gb = groebner_basis::groebner_basis::compute_groebner_basis(fs);
     let end = start.elapsed();
 
     println!("fin");
-    println!("{}.{:03}sec", end.as_secs(), end.subsec_nanos() / 1_000_000);
+    println!("{}.{:03}sec", end.as_secs(), end.subsec_millis());
     for g in gb {
         println!("{}", g);
     }
@@ -1,28 +1,219 @@
-use crate::degree::{Degree, Zn};
-use crate::scalar::Integer;
+use crate::degree::{ExponentVector, PackedExponents};
+use crate::scalar::{Integer, Rational};
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt::{Debug, Display, Error, Formatter};
 use std::ops::{Div, Mul};
+use std::sync::Arc;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+mod order;
+
+pub use order::{
+    same_order_by_eq, BlockOrder, BlockOrderError, Grevlex, Grlex, Lex, MatrixOrder,
+    MatrixOrderError, MonomialOrdering, WeightedOrder, WeightedOrderError,
+};
+
+/// 単項式順序
+///
+/// 重み付き次数順序と行列順序は全ての単項式で共有する。
+/// MonomialOrdering を実装した型は Custom として使える。
+#[derive(Clone, Debug)]
 pub enum MonomialOrder {
     Lex,
     Grlex,
+    Grevlex,
+    Weighted(Arc<WeightedOrder>),
+    Matrix(Arc<MatrixOrder>),
+    Block(Arc<BlockOrder>),
+    Custom(Arc<dyn MonomialOrdering>),
 }
 
-#[derive(Clone, Debug)]
+impl MonomialOrder {
+    /// 重み付き次数で比べ、等しければ tie_breaker で比べる順序
+    pub fn weighted(weights: Vec<i64>, tie_breaker: MonomialOrder) -> Self {
+        MonomialOrder::Weighted(Arc::new(WeightedOrder::new(weights, tie_breaker)))
+    }
+
+    /// 行列の各行との内積を順に比べる順序
+    pub fn matrix<T>(rows: Vec<Vec<T>>) -> Self
+    where
+        Rational: From<T>,
+    {
+        MonomialOrder::Matrix(Arc::new(MatrixOrder::new(rows)))
+    }
+
+    /// 変数をブロックに分け、前のブロックから順にそれぞれの順序で比べる順序 (ブロックの大きさと順序の組を並べる)
+    pub fn block(blocks: Vec<(usize, MonomialOrder)>) -> Self {
+        MonomialOrder::Block(Arc::new(BlockOrder::new(blocks)))
+    }
+
+    /// MonomialOrdering を実装した型の順序
+    pub fn custom<O: MonomialOrdering + 'static>(order: O) -> Self {
+        MonomialOrder::Custom(Arc::new(order))
+    }
+
+    fn as_ordering(&self) -> &dyn MonomialOrdering {
+        match self {
+            MonomialOrder::Lex => &Lex,
+            MonomialOrder::Grlex => &Grlex,
+            MonomialOrder::Grevlex => &Grevlex,
+            MonomialOrder::Weighted(w) => w.as_ref(),
+            MonomialOrder::Matrix(m) => m.as_ref(),
+            MonomialOrder::Block(b) => b.as_ref(),
+            MonomialOrder::Custom(c) => c.as_ref(),
+        }
+    }
+
+    /// 変数の数が決まっている順序ならその数
+    pub(crate) fn n_variables(&self) -> Option<usize> {
+        match self {
+            MonomialOrder::Weighted(w) => Some(w.weights().len()),
+            MonomialOrder::Matrix(m) => m.rows().first().map(|row| row.len()),
+            MonomialOrder::Block(b) => Some(b.len()),
+            _ => None,
+        }
+    }
+
+    /// n 変数の同じ順序を与える n 次正方行列 (Custom は行列にできないので None)
+    pub(crate) fn to_matrix(&self, n: usize) -> Option<Vec<Vec<Integer>>> {
+        let unit = |k: usize| (0..n).map(|j| Integer::from((j == k) as i64)).collect();
+        let ones = |k: usize| (0..n).map(|j| Integer::from((j < k) as i64)).collect();
+        let rows: Vec<Vec<Integer>> = match self {
+            MonomialOrder::Lex => (0..n).map(unit).collect(),
+            MonomialOrder::Grlex => std::iter::once(ones(n)).chain((0..n).map(unit)).collect(),
+            // 全次数の次は後ろの変数を除いた次数が大きい方が大きい
+            MonomialOrder::Grevlex => (1..=n).rev().map(ones).collect(),
+            MonomialOrder::Weighted(w) => {
+                let weights = w.weights().iter().map(|&a| Integer::from(a)).collect();
+                let mut rows = vec![weights];
+                rows.extend(w.tie_breaker().to_matrix(n)?);
+                rows
+            }
+            MonomialOrder::Matrix(m) => m.rows().to_vec(),
+            // 各ブロックの行列を対角に並べる
+            MonomialOrder::Block(b) => {
+                let mut rows = Vec::new();
+                let mut start = 0;
+                for (size, order) in b.blocks() {
+                    for row in order.to_matrix(*size)? {
+                        let mut r = vec![Integer::from(0); n];
+                        for (a, x) in r[start..].iter_mut().zip(row) {
+                            *a = x;
+                        }
+                        rows.push(r);
+                    }
+                    start += size;
+                }
+                rows
+            }
+            MonomialOrder::Custom(_) => return None,
+        };
+
+        // 前の行の一次結合になる行は順序に影響しないので除く
+        let mut ret: Vec<Vec<Integer>> = Vec::new();
+        let mut rational: Vec<Vec<Rational>> = Vec::new();
+        for row in rows {
+            rational.push(row.iter().cloned().map(Rational::from).collect());
+            if order::rank(&rational) > ret.len() {
+                ret.push(row);
+            } else {
+                rational.pop();
+            }
+        }
+        Some(ret)
+    }
+
+    // Lex, Grlex, Grevlex は詰めた指数や全次数を使う速い経路で、結果は
+    // Lex, Grlex, Grevlex の cmp_exponents と同じ。それ以外は cmp_exponents に任せる
+    fn cmp_monomials(&self, lhs: &Monomial, rhs: &Monomial) -> Ordering {
+        let (l, r) = (lhs.exponents(), rhs.exponents());
+        match self {
+            MonomialOrder::Lex => lhs
+                .packed
+                .cmp_lex(&rhs.packed)
+                .unwrap_or_else(|| Lex.cmp_exponents(l, r)),
+            MonomialOrder::Grlex => lhs.total_degree().cmp(&rhs.total_degree()).then_with(|| {
+                lhs.packed
+                    .cmp_lex(&rhs.packed)
+                    .unwrap_or_else(|| Lex.cmp_exponents(l, r))
+            }),
+            MonomialOrder::Grevlex => lhs
+                .total_degree()
+                .cmp(&rhs.total_degree())
+                .then_with(|| Grevlex.cmp_exponents(l, r)),
+            _ => self.cmp_exponents(l, r),
+        }
+    }
+}
+
+impl MonomialOrdering for MonomialOrder {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> Ordering {
+        self.as_ordering().cmp_exponents(lhs, rhs)
+    }
+
+    fn is_degree_order(&self) -> bool {
+        self.as_ordering().is_degree_order()
+    }
+
+    fn is_global(&self) -> bool {
+        self.as_ordering().is_global()
+    }
+}
+
+impl PartialEq for MonomialOrder {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (MonomialOrder::Lex, MonomialOrder::Lex) => true,
+            (MonomialOrder::Grlex, MonomialOrder::Grlex) => true,
+            (MonomialOrder::Grevlex, MonomialOrder::Grevlex) => true,
+            (MonomialOrder::Weighted(l), MonomialOrder::Weighted(r)) => l == r,
+            (MonomialOrder::Matrix(l), MonomialOrder::Matrix(r)) => l == r,
+            (MonomialOrder::Block(l), MonomialOrder::Block(r)) => l == r,
+            // 利用者定義の順序は same_order で比べる
+            (MonomialOrder::Custom(l), MonomialOrder::Custom(r)) => {
+                Arc::ptr_eq(l, r) || l.same_order(r.as_ref())
+            }
+            (_, _) => false,
+        }
+    }
+}
+
+impl Eq for MonomialOrder {}
+
+impl Display for MonomialOrder {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            MonomialOrder::Lex => write!(f, "Lex"),
+            MonomialOrder::Grlex => write!(f, "Grlex"),
+            MonomialOrder::Grevlex => write!(f, "Grevlex"),
+            MonomialOrder::Weighted(w) => {
+                write!(f, "Weighted({:?}, {})", w.weights(), w.tie_breaker())
+            }
+            MonomialOrder::Matrix(m) => write!(f, "Matrix({:?})", m.rows()),
+            MonomialOrder::Block(b) => {
+                let blocks = b
+                    .blocks()
+                    .iter()
+                    .map(|(size, order)| format!("{} {}", size, order))
+                    .collect::<Vec<_>>();
+                write!(f, "Block({})", blocks.join(", "))
+            }
+            MonomialOrder::Custom(c) => write!(f, "{:?}", c),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Monomial {
-    alpha: Zn,
+    alpha: ExponentVector,
+    packed: PackedExponents,
     n: usize,
     monomial_order: MonomialOrder,
 }
 
 impl From<(usize, MonomialOrder)> for Monomial {
     fn from((n_, monomial_order): (usize, MonomialOrder)) -> Self {
-        let mut v = Vec::new();
-        v.resize(n_, Integer::from(0));
-
-        Monomial::from((v, monomial_order))
+        Self::from_alpha(ExponentVector::zeros(n_), monomial_order)
     }
 }
 
@@ -40,46 +231,80 @@ impl From<Vec<Integer>> for Monomial {
 
 impl From<(Vec<i64>, MonomialOrder)> for Monomial {
     fn from(pair: (Vec<i64>, MonomialOrder)) -> Self {
-        let v = pair
-            .0
-            .into_iter()
-            .map(|i| Integer::from(i))
-            .collect::<Vec<_>>();
+        let v = pair.0.into_iter().map(Integer::from).collect::<Vec<_>>();
         Self::from((v, pair.1))
     }
 }
 
 impl From<(Vec<Integer>, MonomialOrder)> for Monomial {
     fn from(pair: (Vec<Integer>, MonomialOrder)) -> Self {
-        let n_ = pair.0.len();
-        Self {
-            alpha: Zn::from(pair.0),
-            n: n_,
-            monomial_order: pair.1,
-        }
+        let alpha = ExponentVector::try_from(&pair.0[..]).unwrap_or_else(|e| panic!("{}", e));
+        Self::from_alpha(alpha, pair.1)
     }
 }
 
-impl Into<Vec<Integer>> for Monomial {
-    fn into(self) -> Vec<Integer> {
-        self.alpha.into()
+impl From<Monomial> for Vec<Integer> {
+    fn from(m: Monomial) -> Self {
+        (&m.alpha).into()
     }
 }
 
-impl Display for Monomial {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        let mut comma_separated = String::new();
+impl Monomial {
+    fn from_alpha(alpha: ExponentVector, monomial_order: MonomialOrder) -> Self {
+        Self {
+            packed: PackedExponents::new(alpha.as_slice()),
+            n: alpha.len(),
+            alpha,
+            monomial_order,
+        }
+    }
+
+    /// 指数
+    pub fn exponents(&self) -> &[u32] {
+        self.alpha.as_slice()
+    }
 
-        let v: &Vec<Integer> = self.alpha.get_ref_v();
+    /// 全次数
+    pub fn total_degree(&self) -> u64 {
+        self.packed.total_degree()
+    }
+
+    /// 割り切れるかの判定と比較に使う詰めた指数
+    pub fn packed(&self) -> &PackedExponents {
+        &self.packed
+    }
+
+    /// 共通の変数を持たないか (lcm が積になるか)
+    pub fn is_coprime(&self, other: &Monomial) -> bool {
+        assert_eq!(self.n, other.n);
+        self.exponents()
+            .iter()
+            .zip(other.exponents().iter())
+            .all(|(a, b)| *a == 0 || *b == 0)
+    }
+
+    /// 変数名を variable_i として表示する
+    pub fn to_string_with(&self, variable: &str) -> String {
+        self.to_string_by(|i| format!("{}_{}", variable, i + 1))
+    }
 
-        for i in 0..v.len() {
-            if v[i] != Integer::from(0) {
-                comma_separated.push_str("(x_");
-                comma_separated.push_str(&(i + 1).to_string());
-                comma_separated.push_str(")");
-                if v[i] != Integer::from(1) {
-                    comma_separated.push_str("^");
-                    comma_separated.push_str(&v[i].to_string());
+    /// i 番目 (0始まり) の変数名を names[i] として表示する
+    pub fn to_string_with_names(&self, names: &[String]) -> String {
+        assert_eq!(names.len(), self.n);
+        self.to_string_by(|i| names[i].clone())
+    }
+
+    fn to_string_by(&self, name: impl Fn(usize) -> String) -> String {
+        let mut comma_separated = String::new();
+
+        for (i, vi) in self.exponents().iter().enumerate() {
+            if *vi != 0 {
+                comma_separated.push('(');
+                comma_separated.push_str(&name(i));
+                comma_separated.push(')');
+                if *vi != 1 {
+                    comma_separated.push('^');
+                    comma_separated.push_str(&vi.to_string());
                 }
             }
         }
@@ -89,65 +314,52 @@ impl Display for Monomial {
         //    MonomialOrder::Grlex => "Grlex",
         //});
 
-        write!(f, "{}", comma_separated)
+        comma_separated
     }
 }
 
-fn lex(lhs: &Vec<Integer>, rhs: &Vec<Integer>) -> Ordering {
-    let n = std::cmp::min(lhs.len(), rhs.len());
-
-    for i in 0..n {
-        if lhs[i] != rhs[i] {
-            return lhs[i].cmp(&rhs[i]);
-        }
+impl Debug for Monomial {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.debug_struct("Monomial")
+            .field("alpha", &self.alpha)
+            .field("n", &self.n)
+            .field("monomial_order", &self.monomial_order)
+            .finish()
     }
-
-    lhs.len().cmp(&rhs.len())
 }
 
-fn grlex(lhs: &Vec<Integer>, rhs: &Vec<Integer>) -> Ordering {
-    let l_sum: Integer = lhs.iter().fold(Integer::from(0), |sum, a| sum + a);
-    let r_sum: Integer = rhs.iter().fold(Integer::from(0), |sum, a| sum + a);
-    if l_sum != r_sum {
-        return l_sum.cmp(&r_sum);
+impl Display for Monomial {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}", self.to_string_with("x"))
     }
-    lex(lhs, rhs)
 }
 
-impl<'a, 'b> Mul<&'a Monomial> for &'b Monomial {
+impl Mul<&Monomial> for &Monomial {
     type Output = Monomial;
 
     fn mul(self, other: &Monomial) -> Monomial {
         assert!(self.n == other.n);
         assert!(self.monomial_order == other.monomial_order);
 
-        Monomial {
-            alpha: &self.alpha + &other.alpha,
-            n: self.n,
-            monomial_order: self.monomial_order,
-        }
+        Monomial::from_alpha(&self.alpha + &other.alpha, self.monomial_order.clone())
     }
 }
 
-impl<'a, 'b> Div<&'a Monomial> for &'b Monomial {
+impl Div<&Monomial> for &Monomial {
     type Output = Monomial;
 
     fn div(self, other: &Monomial) -> Monomial {
         assert!(self.n == other.n);
         assert!(self.monomial_order == other.monomial_order);
 
-        Monomial {
-            alpha: &self.alpha - &other.alpha,
-            n: self.n,
-            monomial_order: self.monomial_order,
-        }
+        Monomial::from_alpha(&self.alpha - &other.alpha, self.monomial_order.clone())
     }
 }
 
 impl PartialEq for Monomial {
+    // 順序が異なる単項式は等しくない
     fn eq(&self, other: &Self) -> bool {
-        assert!(self.monomial_order == other.monomial_order);
-        self.cmp(&other) == Ordering::Equal
+        self.exponents() == other.exponents() && self.monomial_order == other.monomial_order
     }
 }
 
@@ -161,10 +373,7 @@ impl PartialOrd for Monomial {
 
 impl Ord for Monomial {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.monomial_order {
-            MonomialOrder::Lex => lex(self.alpha.get_ref_v(), other.alpha.get_ref_v()),
-            MonomialOrder::Grlex => grlex(self.alpha.get_ref_v(), other.alpha.get_ref_v()),
-        }
+        self.monomial_order.cmp_monomials(self, other)
     }
 }
 
@@ -182,7 +391,7 @@ impl MonomialHandlers for Monomial {
         self.monomial_order = o;
     }
     fn get_monomial_order(&self) -> MonomialOrder {
-        self.monomial_order
+        self.monomial_order.clone()
     }
     fn get_n(&self) -> usize {
         self.n
@@ -190,21 +399,13 @@ impl MonomialHandlers for Monomial {
     fn is_divisible_by(&self, rhs: &Monomial) -> bool {
         assert_eq!(self.get_n(), rhs.get_n());
 
-        let lv = self.alpha.get_ref_v();
-        let rv = rhs.alpha.get_ref_v();
-        for i in 0..(lv.len()) {
-            if lv[i] < rv[i] {
-                return false;
-            }
-        }
-        true
+        self.packed
+            .is_divisible_by(&rhs.packed)
+            .unwrap_or_else(|| self.alpha.is_divisible_by(&rhs.alpha))
     }
 
     fn fetch_total_degree(&self) -> Integer {
-        self.alpha
-            .get_ref_v()
-            .iter()
-            .fold(Integer::from(0), |sum, a| sum + a)
+        Integer::from(self.total_degree())
     }
 }
 
@@ -212,15 +413,7 @@ pub fn lcm(a: &Monomial, b: &Monomial) -> Monomial {
     assert_eq!(a.n, b.n);
     assert_eq!(a.monomial_order, b.monomial_order);
 
-    let va = a.alpha.get_ref_v();
-    let vb = b.alpha.get_ref_v();
-
-    assert_eq!(va.len(), vb.len());
-
-    let mut v = Vec::new();
-    for (ai, bi) in va.iter().zip(vb.iter()) {
-        v.push(std::cmp::max(ai, bi).clone());
-    }
-
-    Monomial::from((v, a.monomial_order))
+    Monomial::from_alpha(a.alpha.max(&b.alpha), a.monomial_order.clone())
 }
+
+mod test;
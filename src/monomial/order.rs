@@ -0,0 +1,429 @@
+use crate::monomial::MonomialOrder;
+use crate::scalar::{self, Field, Integer, Rational};
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Error, Formatter};
+
+/// 単項式順序
+///
+/// 同じ長さの指数ベクトルを比べる全順序。実装して MonomialOrder::custom に渡せば、
+/// Monomial や Polynomial でそのまま使える。
+pub trait MonomialOrdering: Any + Debug + Send + Sync {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> Ordering;
+
+    /// 全次数を最初に比べる順序か
+    fn is_degree_order(&self) -> bool {
+        false
+    }
+
+    /// 整列順序 (1 が最小の単項式) か。グレブナー基底の計算にはこれが必要
+    fn is_global(&self) -> bool {
+        true
+    }
+
+    /// other と同じ順序か
+    ///
+    /// 既定では同じ値を指している時だけ真。別々に作った値も等しいとみなすなら実装し直す
+    /// (PartialEq を持つ型は same_order_by_eq を使えばよい)。
+    fn same_order(&self, other: &dyn MonomialOrdering) -> bool {
+        std::ptr::eq(
+            self as *const Self as *const u8,
+            other as *const dyn MonomialOrdering as *const u8,
+        )
+    }
+}
+
+/// 型が同じで PartialEq でも等しいか (MonomialOrdering::same_order の実装用)
+pub fn same_order_by_eq<O>(order: &O, other: &dyn MonomialOrdering) -> bool
+where
+    O: MonomialOrdering + PartialEq,
+{
+    let other: &dyn Any = other;
+    other.downcast_ref::<O>() == Some(order)
+}
+
+fn total_degree(alpha: &[u32]) -> u64 {
+    alpha.iter().map(|&a| a as u64).sum()
+}
+
+/// 辞書式順序
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Lex;
+
+impl MonomialOrdering for Lex {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> Ordering {
+        for (l, r) in lhs.iter().zip(rhs.iter()) {
+            if l != r {
+                return l.cmp(r);
+            }
+        }
+
+        lhs.len().cmp(&rhs.len())
+    }
+}
+
+/// 次数付き辞書式順序
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Grlex;
+
+impl MonomialOrdering for Grlex {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> Ordering {
+        total_degree(lhs)
+            .cmp(&total_degree(rhs))
+            .then_with(|| Lex.cmp_exponents(lhs, rhs))
+    }
+
+    fn is_degree_order(&self) -> bool {
+        true
+    }
+}
+
+/// 次数付き逆辞書式順序
+///
+/// 全次数が等しければ、最後に異なる変数の指数が小さい方が大きい。
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Grevlex;
+
+impl MonomialOrdering for Grevlex {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> Ordering {
+        let ord = total_degree(lhs).cmp(&total_degree(rhs));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+        for (l, r) in lhs.iter().zip(rhs.iter()).rev() {
+            if l != r {
+                return r.cmp(l);
+            }
+        }
+
+        lhs.len().cmp(&rhs.len())
+    }
+
+    fn is_degree_order(&self) -> bool {
+        true
+    }
+}
+
+/// 重み付き次数順序
+///
+/// 重みとの内積で比べ、等しければ tie_breaker で比べる。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WeightedOrder {
+    weights: Vec<u64>,
+    tie_breaker: MonomialOrder,
+}
+
+/// 重みが単項式順序にならない理由
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum WeightedOrderError {
+    /// 負の重みがある (その位置)
+    NegativeWeight(usize),
+    /// 重みの数と tie_breaker の変数の数が異なる
+    Length(usize, usize),
+}
+
+impl Display for WeightedOrderError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            WeightedOrderError::NegativeWeight(i) => {
+                write!(f, "weights must be non-negative (weight {})", i)
+            }
+            WeightedOrderError::Length(w, n) => {
+                write!(f, "{} weights for a tie-breaker on {} variables", w, n)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WeightedOrderError {}
+
+impl WeightedOrder {
+    /// 単項式順序にならない重みでは panic する
+    pub fn new(weights: Vec<i64>, tie_breaker: MonomialOrder) -> Self {
+        Self::try_new(weights, tie_breaker).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(
+        weights: Vec<i64>,
+        tie_breaker: MonomialOrder,
+    ) -> Result<Self, WeightedOrderError> {
+        // 負の重みがあると整列順序にならない
+        if let Some(i) = weights.iter().position(|&w| w < 0) {
+            return Err(WeightedOrderError::NegativeWeight(i));
+        }
+        if let Some(n) = tie_breaker.n_variables() {
+            if n != weights.len() {
+                return Err(WeightedOrderError::Length(weights.len(), n));
+            }
+        }
+        Ok(Self {
+            weights: weights.into_iter().map(|w| w as u64).collect(),
+            tie_breaker,
+        })
+    }
+
+    pub fn weights(&self) -> &[u64] {
+        &self.weights
+    }
+
+    pub fn tie_breaker(&self) -> &MonomialOrder {
+        &self.tie_breaker
+    }
+
+    pub fn weighted_degree(&self, alpha: &[u32]) -> u128 {
+        assert_eq!(self.weights.len(), alpha.len());
+        self.weights
+            .iter()
+            .zip(alpha.iter())
+            .map(|(&w, &a)| w as u128 * a as u128)
+            .sum()
+    }
+}
+
+impl MonomialOrdering for WeightedOrder {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> Ordering {
+        self.weighted_degree(lhs)
+            .cmp(&self.weighted_degree(rhs))
+            .then_with(|| self.tie_breaker.cmp_exponents(lhs, rhs))
+    }
+
+    fn is_degree_order(&self) -> bool {
+        let w = self.weights.first();
+        w.is_some_and(|&w| w > 0 && self.weights.iter().all(|&v| v == w))
+    }
+
+    fn is_global(&self) -> bool {
+        self.tie_breaker.is_global()
+    }
+}
+
+/// 行列順序
+///
+/// 行列の各行との内積を上の行から順に比べる。
+/// 各行は分母を払った整数で持つ (正の定数倍しても順序は変わらない)。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MatrixOrder {
+    rows: Vec<Vec<Integer>>,
+}
+
+/// 行列が単項式順序にならない理由
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MatrixOrderError {
+    /// 行の長さが揃っていない
+    RowLengths,
+    /// 列が一次独立でない
+    Rank,
+    /// 最初の0でない成分が正でない列がある
+    ColumnSign(usize),
+}
+
+impl Display for MatrixOrderError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            MatrixOrderError::RowLengths => write!(f, "rows of different lengths"),
+            MatrixOrderError::Rank => write!(f, "matrix order must have full column rank"),
+            MatrixOrderError::ColumnSign(j) => write!(
+                f,
+                "first non-zero entry of each column must be positive (column {})",
+                j
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MatrixOrderError {}
+
+impl MatrixOrder {
+    /// 単項式順序にならない行列では panic する
+    pub fn new<T>(rows: Vec<Vec<T>>) -> Self
+    where
+        Rational: From<T>,
+    {
+        Self::try_new(rows).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<T>(rows: Vec<Vec<T>>) -> Result<Self, MatrixOrderError>
+    where
+        Rational: From<T>,
+    {
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(Rational::from).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let n = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != n) {
+            return Err(MatrixOrderError::RowLengths);
+        }
+        // 整列的な全順序になるには、列が一次独立で各列の最初の0でない成分が正である必要がある
+        if rank(&rows) != n {
+            return Err(MatrixOrderError::Rank);
+        }
+        for j in 0..n {
+            let first = rows.iter().map(|row| &row[j]).find(|a| !a.is_zero());
+            if first.is_none_or(|a| a <= &Rational::zero()) {
+                return Err(MatrixOrderError::ColumnSign(j));
+            }
+        }
+
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                let lcm_den = row
+                    .iter()
+                    .fold(Integer::from(1), |l, a| scalar::lcm(&l, &a.get_den()));
+                row.iter()
+                    .map(|a| (a.get_num() * &lcm_den) / a.get_den())
+                    .collect()
+            })
+            .collect();
+        Ok(Self { rows })
+    }
+
+    pub fn rows(&self) -> &[Vec<Integer>] {
+        &self.rows
+    }
+}
+
+impl MonomialOrdering for MatrixOrder {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> Ordering {
+        for row in &self.rows {
+            assert_eq!(row.len(), lhs.len());
+            let ord = dot(row, lhs).cmp(&dot(row, rhs));
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+        Ordering::Equal
+    }
+
+    fn is_degree_order(&self) -> bool {
+        self.rows
+            .first()
+            .is_some_and(|row| row[0] > Integer::from(0) && row.iter().all(|a| a == &row[0]))
+    }
+}
+
+/// ブロック順序 (積順序)
+///
+/// 変数を先頭から順にブロックに分け、前のブロックの指数から順にそれぞれの順序で比べる。
+/// 先頭のブロックの変数を消去する時に使う。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BlockOrder {
+    blocks: Vec<(usize, MonomialOrder)>,
+}
+
+/// ブロックの並びが単項式順序にならない理由
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BlockOrderError {
+    /// 変数のないブロックがある (その位置)
+    EmptyBlock(usize),
+    /// ブロックの大きさと順序の変数の数が異なる (その位置)
+    Size(usize),
+}
+
+impl Display for BlockOrderError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            BlockOrderError::EmptyBlock(i) => write!(f, "blocks must not be empty (block {})", i),
+            BlockOrderError::Size(i) => write!(
+                f,
+                "monomial order does not match the block size (block {})",
+                i
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BlockOrderError {}
+
+impl BlockOrder {
+    /// 単項式順序にならないブロックの並びでは panic する
+    pub fn new(blocks: Vec<(usize, MonomialOrder)>) -> Self {
+        Self::try_new(blocks).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(blocks: Vec<(usize, MonomialOrder)>) -> Result<Self, BlockOrderError> {
+        for (i, (size, order)) in blocks.iter().enumerate() {
+            if *size == 0 {
+                return Err(BlockOrderError::EmptyBlock(i));
+            }
+            if order.n_variables().is_some_and(|n| n != *size) {
+                return Err(BlockOrderError::Size(i));
+            }
+        }
+        Ok(Self { blocks })
+    }
+
+    pub fn blocks(&self) -> &[(usize, MonomialOrder)] {
+        &self.blocks
+    }
+
+    /// 変数の数
+    pub fn len(&self) -> usize {
+        self.blocks.iter().map(|(size, _)| size).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+}
+
+impl MonomialOrdering for BlockOrder {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> Ordering {
+        assert_eq!(self.len(), lhs.len());
+        let mut start = 0;
+        for (size, order) in &self.blocks {
+            let range = start..(start + size);
+            let ord = order.cmp_exponents(&lhs[range.clone()], &rhs[range]);
+            if ord != Ordering::Equal {
+                return ord;
+            }
+            start += size;
+        }
+        Ordering::Equal
+    }
+
+    fn is_degree_order(&self) -> bool {
+        self.blocks.len() == 1 && self.blocks[0].1.is_degree_order()
+    }
+
+    fn is_global(&self) -> bool {
+        self.blocks.iter().all(|(_, order)| order.is_global())
+    }
+}
+
+fn dot(row: &[Integer], alpha: &[u32]) -> Integer {
+    row.iter()
+        .zip(alpha.iter())
+        .filter(|(_, &a)| a != 0)
+        .fold(Integer::from(0), |sum, (r, &a)| {
+            sum + r * &Integer::from(a as u64)
+        })
+}
+
+// 有理数行列の階数 (掃き出し法)
+pub(super) fn rank(rows: &[Vec<Rational>]) -> usize {
+    let mut rows = rows.to_vec();
+    let n = rows.first().map_or(0, |row| row.len());
+    let mut r = 0;
+    for j in 0..n {
+        let pivot = match (r..rows.len()).find(|&i| !rows[i][j].is_zero()) {
+            Some(pivot) => pivot,
+            None => continue,
+        };
+        rows.swap(r, pivot);
+        let (top, bottom) = rows.split_at_mut(r + 1);
+        let pivot_row = &top[r];
+        let inv = pivot_row[j].inv();
+        for row in bottom {
+            let c = row[j].clone() * inv.clone();
+            for (a, b) in row[j..].iter_mut().zip(pivot_row[j..].iter()) {
+                *a = a.clone() - c.clone() * b.clone();
+            }
+        }
+        r += 1;
+    }
+    r
+}
@@ -0,0 +1,202 @@
+#[allow(unused_imports)]
+use super::{Monomial, MonomialOrder};
+#[cfg(test)]
+#[allow(unused_imports)]
+use crate::scalar::Rational;
+#[allow(unused_imports)]
+use std::cmp::Ordering;
+
+#[allow(dead_code)]
+fn all_monomials(n: usize, d: i64, order: &MonomialOrder) -> Vec<Monomial> {
+    let mut ret = vec![vec![]];
+    for _ in 0..n {
+        ret = ret
+            .into_iter()
+            .flat_map(|v: Vec<i64>| {
+                (0..=d).map(move |e| {
+                    let mut v = v.clone();
+                    v.push(e);
+                    v
+                })
+            })
+            .collect();
+    }
+    ret.into_iter()
+        .map(|v| Monomial::from((v, order.clone())))
+        .collect()
+}
+
+#[test]
+fn test_weighted_order() {
+    let order = MonomialOrder::weighted(vec![1, 2, 3], MonomialOrder::Lex);
+    let m = |v: Vec<i64>| Monomial::from((v, order.clone()));
+
+    // x y と z は重み3で等しく辞書式で比べる。y (重み2) より x^3 (重み3) が大きい
+    assert!(m(vec![1, 1, 0]) > m(vec![0, 0, 1]));
+    assert!(m(vec![0, 0, 1]) > m(vec![0, 1, 0]));
+    assert!(m(vec![3, 0, 0]) > m(vec![0, 1, 0]));
+    assert!(m(vec![0, 1, 0]) > m(vec![1, 0, 0]));
+
+    // 重みが全て1なら tie_breaker の次数付き版になる
+    let weighted = MonomialOrder::weighted(vec![1, 1, 1], MonomialOrder::Grevlex);
+    let xs = all_monomials(3, 2, &MonomialOrder::Grevlex);
+    let ys = all_monomials(3, 2, &weighted);
+    for (x0, y0) in xs.iter().zip(ys.iter()) {
+        for (x1, y1) in xs.iter().zip(ys.iter()) {
+            assert_eq!(x0.cmp(x1), y0.cmp(y1));
+        }
+    }
+}
+
+#[test]
+fn test_matrix_order() {
+    // grevlex を表す行列
+    let grevlex = MonomialOrder::matrix(vec![
+        vec![1, 1, 1, 1],
+        vec![0, 0, 0, -1],
+        vec![0, 0, -1, 0],
+        vec![0, -1, 0, 0],
+    ]);
+    // lex を表す有理数行列 (正の定数倍しても同じ順序)
+    let lex = MonomialOrder::matrix(vec![
+        vec![Rational::from(1) / Rational::from(2), Rational::from(0)],
+        vec![Rational::from(0), Rational::from(3)],
+    ]);
+
+    for (order, expected) in [(grevlex, MonomialOrder::Grevlex), (lex, MonomialOrder::Lex)] {
+        let n = match &order {
+            MonomialOrder::Matrix(m) => m.rows()[0].len(),
+            _ => unreachable!(),
+        };
+        let xs = all_monomials(n, 2, &expected);
+        let ys = all_monomials(n, 2, &order);
+        for (x0, y0) in xs.iter().zip(ys.iter()) {
+            for (x1, y1) in xs.iter().zip(ys.iter()) {
+                assert_eq!(x0.cmp(x1), y0.cmp(y1), "{} {}", x0, x1);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "full column rank")]
+fn test_matrix_order_degenerate() {
+    MonomialOrder::matrix(vec![vec![1, 1], vec![2, 2]]);
+}
+
+#[test]
+fn test_block_order() {
+    let order = MonomialOrder::block(vec![
+        (1, MonomialOrder::Grevlex),
+        (2, MonomialOrder::Grevlex),
+    ]);
+    let m = |v: Vec<i64>| Monomial::from((v, order.clone()));
+
+    // 先頭のブロックが優先される
+    assert!(m(vec![1, 0, 0]) > m(vec![0, 5, 5]));
+    // 先頭のブロックが等しければ残りを次数逆辞書式順序で比べる
+    assert!(m(vec![1, 2, 0]) > m(vec![1, 1, 1]));
+    assert!(m(vec![1, 2, 1]) > m(vec![1, 2, 0]));
+    assert_eq!(order.to_string(), "Block(1 Grevlex, 2 Grevlex)");
+
+    // 作る時に大きさを確かめる
+    use super::{BlockOrder, BlockOrderError, WeightedOrder, WeightedOrderError};
+    assert_eq!(
+        WeightedOrder::try_new(vec![1, -2], MonomialOrder::Lex).unwrap_err(),
+        WeightedOrderError::NegativeWeight(1)
+    );
+    assert_eq!(
+        WeightedOrder::try_new(vec![1, 2], order.clone()).unwrap_err(),
+        WeightedOrderError::Length(2, 3)
+    );
+    assert!(WeightedOrder::try_new(vec![1, 2, 0], order.clone()).is_ok());
+    assert_eq!(
+        BlockOrder::try_new(vec![(1, MonomialOrder::Lex), (0, MonomialOrder::Lex)]).unwrap_err(),
+        BlockOrderError::EmptyBlock(1)
+    );
+    assert_eq!(
+        BlockOrder::try_new(vec![(2, order.clone()), (1, MonomialOrder::Lex)]).unwrap_err(),
+        BlockOrderError::Size(0)
+    );
+    assert!(BlockOrder::try_new(vec![(3, order), (1, MonomialOrder::Lex)]).is_ok());
+}
+
+// 変数を逆に並べた辞書式順序
+#[allow(dead_code)]
+#[derive(PartialEq, Debug)]
+struct ReverseLex;
+
+impl super::MonomialOrdering for ReverseLex {
+    fn cmp_exponents(&self, lhs: &[u32], rhs: &[u32]) -> Ordering {
+        lhs.iter().rev().cmp(rhs.iter().rev())
+    }
+
+    fn same_order(&self, other: &dyn super::MonomialOrdering) -> bool {
+        super::same_order_by_eq(self, other)
+    }
+}
+
+#[test]
+fn test_custom_order() {
+    use super::MonomialOrdering;
+
+    let order = MonomialOrder::custom(ReverseLex);
+    let m = |v: Vec<i64>| Monomial::from((v, order.clone()));
+    assert!(m(vec![0, 1]) > m(vec![5, 0]));
+    assert!(m(vec![1, 1]) > m(vec![0, 1]));
+    assert!(order.is_global());
+    assert!(!order.is_degree_order());
+
+    // same_order で比べる
+    assert_eq!(order, order.clone());
+    assert_eq!(order, MonomialOrder::custom(ReverseLex));
+    assert_ne!(order, MonomialOrder::custom(super::Lex));
+    // 既定では同じものを共有している時だけ等しい
+    assert_ne!(
+        MonomialOrder::custom(super::Lex),
+        MonomialOrder::custom(super::Lex)
+    );
+    // 順序の異なる単項式は等しくない
+    assert_eq!(
+        m(vec![1, 2]),
+        Monomial::from((vec![1, 2], MonomialOrder::custom(ReverseLex)))
+    );
+    assert_ne!(
+        m(vec![1, 2]),
+        Monomial::from((vec![1, 2], MonomialOrder::Lex))
+    );
+
+    assert!(MonomialOrder::Grevlex.is_degree_order());
+    assert!(MonomialOrder::weighted(vec![2, 2], MonomialOrder::Lex).is_degree_order());
+    assert!(!MonomialOrder::weighted(vec![1, 2], MonomialOrder::Lex).is_degree_order());
+}
+
+#[test]
+fn test_fast_paths() {
+    use super::{Grevlex, Grlex, Lex, MonomialOrdering};
+
+    let cases: Vec<(MonomialOrder, &dyn MonomialOrdering)> = vec![
+        (MonomialOrder::Lex, &Lex),
+        (MonomialOrder::Grlex, &Grlex),
+        (MonomialOrder::Grevlex, &Grevlex),
+    ];
+    for (order, ordering) in cases {
+        let mut ms = all_monomials(3, 3, &order);
+        // 詰められない大きな指数
+        ms.push(Monomial::from((vec![1 << 20, 0, 1], order.clone())));
+        ms.push(Monomial::from((vec![1 << 20, 1, 0], order.clone())));
+        ms.push(Monomial::from((vec![0, 0, (1 << 31) + 1], order.clone())));
+        for a in &ms {
+            for b in &ms {
+                assert_eq!(
+                    a.cmp(b),
+                    ordering.cmp_exponents(a.exponents(), b.exponents()),
+                    "{} {:?} {:?}",
+                    order,
+                    a.exponents(),
+                    b.exponents()
+                );
+            }
+        }
+    }
+}
@@ -1,17 +1,115 @@
 use crate::monomial::{Monomial, MonomialHandlers, MonomialOrder};
-use crate::scalar::{Integer, Rational};
+use crate::scalar::{Field, Integer, Rational, Ring};
 use crate::{monomial, scalar};
-use std::collections::BTreeMap;
 
-use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Error, Formatter};
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::sync::Arc;
+
+mod gcd;
+mod geobucket;
+pub(crate) mod parser;
+mod ring;
+mod terms;
+pub(crate) use gcd::divide_exact;
+pub use gcd::gcd;
+use geobucket::Geobucket;
+pub use parser::ParseError;
+pub use ring::{PolynomialRing, RingError};
+use terms::Terms;
 
 #[derive(Clone, Debug)]
-pub struct Polynomial {
-    terms: BTreeMap<Monomial, Rational>,
+pub struct Polynomial<K: Ring = Rational> {
+    terms: Terms<K>,
     n: usize,
     monomial_order: MonomialOrder,
+    // 変数名を持つ環 (無ければ演算の相手の環に合わせる)
+    ring: Option<Arc<PolynomialRing>>,
+}
+
+impl<K: Ring> Polynomial<K> {
+    /// 0多項式
+    pub fn new(n: usize, monomial_order: MonomialOrder) -> Self {
+        Self {
+            terms: Terms::new(),
+            n,
+            monomial_order,
+            ring: None,
+        }
+    }
+
+    /// 属する多項式環
+    pub fn ring(&self) -> Option<&Arc<PolynomialRing>> {
+        self.ring.as_ref()
+    }
+
+    /// 多項式環を付け替える (変数の数と単項式順序は同じでなければならない)
+    pub fn with_ring(mut self, ring: &Arc<PolynomialRing>) -> Result<Self, RingError> {
+        let zero = ring.zero();
+        ring::common_ring(&self, &zero)?;
+        self.ring = Some(ring.clone());
+        Ok(self)
+    }
+
+    /// 単項式順序を o に変えた多項式 (項を新しい順序で並べ直し、環も付け替える)
+    pub fn with_monomial_order(self, o: MonomialOrder) -> Self {
+        let ring = self
+            .ring
+            .as_ref()
+            .map(|ring| ring.with_monomial_order(o.clone()));
+        self.reorder(o, ring)
+    }
+
+    fn reorder(self, o: MonomialOrder, ring: Option<Arc<PolynomialRing>>) -> Self {
+        if o == self.monomial_order {
+            return Self { ring, ..self };
+        }
+        let terms = self
+            .terms
+            .into_iter()
+            .map(|(mut monomial, coeff)| {
+                monomial.set_monomial_order(o.clone());
+                (monomial, coeff)
+            })
+            .collect();
+        Self {
+            terms,
+            n: self.n,
+            monomial_order: o,
+            ring,
+        }
+    }
+
+    /// 項を単項式順序の昇順に辿る
+    pub fn terms(&self) -> impl DoubleEndedIterator<Item = (&Monomial, &K)> {
+        self.terms.iter()
+    }
+
+    /// 係数を写像した多項式 (係数が0になった項は落とす)
+    pub fn map_coeff<L: Ring, F: FnMut(&K) -> L>(&self, f: F) -> Polynomial<L> {
+        Polynomial {
+            terms: self.terms.map(f),
+            n: self.n,
+            monomial_order: self.monomial_order.clone(),
+            ring: self.ring.clone(),
+        }
+    }
+
+    fn mul_coeff(&self, c: &K) -> Self {
+        self.map_coeff(|coeff| coeff.clone() * c.clone())
+    }
+
+    pub fn try_map_coeff<L: Ring, E, F: FnMut(&K) -> Result<L, E>>(
+        &self,
+        f: F,
+    ) -> Result<Polynomial<L>, E> {
+        Ok(Polynomial {
+            terms: self.terms.try_map(f)?,
+            n: self.n,
+            monomial_order: self.monomial_order.clone(),
+            ring: self.ring.clone(),
+        })
+    }
 }
 
 impl From<usize> for Polynomial {
@@ -22,11 +120,7 @@ impl From<usize> for Polynomial {
 
 impl From<(usize, MonomialOrder)> for Polynomial {
     fn from(pair: (usize, MonomialOrder)) -> Self {
-        Self {
-            terms: BTreeMap::new(),
-            n: pair.0,
-            monomial_order: pair.1,
-        }
+        Self::new(pair.0, pair.1)
     }
 }
 
@@ -56,33 +150,21 @@ impl From<(Monomial, MonomialOrder)> for Polynomial {
     }
 }
 
-impl From<(Rational, usize, MonomialOrder)> for Polynomial {
-    fn from(pair: (Rational, usize, MonomialOrder)) -> Self {
-        let mut v = Vec::new();
-        v.resize(pair.1, Integer::from(0));
-
-        Self::from((pair.0, Monomial::from((v, pair.2)), pair.2))
+impl<K: Ring> From<(K, usize, MonomialOrder)> for Polynomial<K> {
+    fn from(pair: (K, usize, MonomialOrder)) -> Self {
+        Self::from((pair.0, Monomial::from((pair.1, pair.2.clone())), pair.2))
     }
 }
 
-impl From<(Rational, usize)> for Polynomial {
-    fn from(pair: (Rational, usize)) -> Self {
-        let mut v = Vec::new();
-        v.resize(pair.1, Integer::from(0));
-
-        let monomial_order = MonomialOrder::Lex;
-        Self::from((pair.0, Monomial::from((v, monomial_order)), monomial_order))
+impl<K: Ring> From<(K, usize)> for Polynomial<K> {
+    fn from(pair: (K, usize)) -> Self {
+        Self::from((pair.0, pair.1, MonomialOrder::Lex))
     }
 }
 
-impl From<(Rational, Monomial, MonomialOrder)> for Polynomial {
-    fn from(tuple: (Rational, Monomial, MonomialOrder)) -> Self {
-        let n_ = tuple.1.get_n();
-        let mut ret = Self {
-            terms: BTreeMap::new(),
-            n: n_,
-            monomial_order: tuple.2,
-        };
+impl<K: Ring> From<(K, Monomial, MonomialOrder)> for Polynomial<K> {
+    fn from(tuple: (K, Monomial, MonomialOrder)) -> Self {
+        let mut ret = Self::new(tuple.1.get_n(), tuple.2.clone());
 
         let mut monomial = tuple.1;
         monomial.set_monomial_order(tuple.2);
@@ -93,354 +175,472 @@ impl From<(Rational, Monomial, MonomialOrder)> for Polynomial {
     }
 }
 
-impl Display for Polynomial {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+impl<K: Ring> Polynomial<K> {
+    /// 変数名を variable_i として単項式順序を付けずに表示する
+    pub fn to_string_with(&self, variable: &str) -> String {
+        self.to_string_by(|m| m.to_string_with(variable))
+    }
+
+    /// i 番目 (0始まり) の変数名を names[i] として単項式順序を付けずに表示する
+    pub fn to_string_with_names(&self, names: &[String]) -> String {
+        self.to_string_by(|m| m.to_string_with_names(names))
+    }
+
+    fn to_string_by(&self, monomial_to_string: impl Fn(&Monomial) -> String) -> String {
         let mut output = String::new();
 
         let mut front = true;
 
-        let terms = self.terms.iter().collect::<Vec<(&Monomial, &Rational)>>();
-
-        for it in terms.iter().rev() {
-            let monomial = it.0;
-            let coeff = it.1;
+        for (monomial, coeff) in self.terms.iter().rev() {
+            if coeff.is_zero() {
+                continue;
+            }
 
-            let is_show_abs_coeff = |abs_coeff: &Rational, monomial: &Monomial| {
-                // 定数項であるまたは係数の絶対値が1で無い時
-                monomial.fetch_total_degree() == Integer::from(0) || abs_coeff != &Rational::from(1)
+            // 係数の符号は表示上の先頭の'-'で判断する (複数の項からなる係数は括弧で囲む)
+            let coeff = coeff.to_string();
+            let (is_negative, abs_coeff) = match coeff.strip_prefix('-') {
+                Some(abs_coeff) if !abs_coeff.contains([' ', '+', '-']) => (true, abs_coeff),
+                _ => (false, coeff.as_str()),
             };
 
-            match coeff.cmp(&Rational::from(0)) {
-                Ordering::Equal => {
-                    continue;
-                }
-                Ordering::Greater => {
-                    if !front {
-                        output.push_str(" + ");
-                    }
-                }
-                Ordering::Less => {
-                    output.push_str(" - ");
-                }
-            };
+            if is_negative {
+                output.push_str(" - ");
+            } else if !front {
+                output.push_str(" + ");
+            }
             front = false;
 
-            let abs_coeff = coeff.abs();
-            if is_show_abs_coeff(&abs_coeff, monomial) {
-                output.push_str(&abs_coeff.to_string());
+            // 定数項であるまたは係数の絶対値が1で無い時
+            if monomial.fetch_total_degree() == Integer::from(0) || abs_coeff != "1" {
+                if abs_coeff.contains([' ', '+', '-']) {
+                    output.push('(');
+                    output.push_str(abs_coeff);
+                    output.push(')');
+                } else {
+                    output.push_str(abs_coeff);
+                }
             }
-            output.push_str(&monomial.to_string());
+            output.push_str(&monomial_to_string(monomial));
         }
 
         if front {
-            output.push_str("0");
+            output.push('0');
         }
 
-        output.push_str(" ");
+        output
+    }
+}
 
-        output.push_str(match self.monomial_order {
-            MonomialOrder::Lex => "Lex",
-            MonomialOrder::Grlex => "Grlex",
-        });
+impl<K: Ring> Display for Polynomial<K> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let mut output = match &self.ring {
+            Some(ring) => self.to_string_with_names(ring.names()),
+            None => self.to_string_with("x"),
+        };
+
+        output.push(' ');
+
+        output.push_str(&self.monomial_order.to_string());
 
         write!(f, "{}", output)
     }
 }
 
-impl<'a, 'b> Add<&'a Polynomial> for &'b Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Polynomial<K> {
+    /// 和 (異なる環の多項式ならエラー)
+    pub fn try_add(&self, other: &Polynomial<K>) -> Result<Polynomial<K>, RingError> {
+        let ring = ring::common_ring(self, other)?;
 
-    fn add(self, other: &Polynomial) -> Polynomial {
-        assert!(self.n == other.n);
-        assert!(self.monomial_order == other.monomial_order);
+        Ok(Polynomial {
+            terms: self.terms.merge(&other.terms, |c| c.clone()),
+            n: self.n,
+            monomial_order: self.monomial_order.clone(),
+            ring,
+        })
+    }
 
-        let longer: &Polynomial = if self.terms.len() >= other.terms.len() {
-            self
-        } else {
-            other
-        };
-        let shorter: &Polynomial = if self.terms.len() >= other.terms.len() {
-            other
-        } else {
-            self
-        };
+    /// 差 (異なる環の多項式ならエラー)
+    pub fn try_sub(&self, other: &Polynomial<K>) -> Result<Polynomial<K>, RingError> {
+        let ring = ring::common_ring(self, other)?;
 
-        let mut ret = longer.clone();
+        Ok(Polynomial {
+            terms: self.terms.merge(&other.terms, |c| -c.clone()),
+            n: self.n,
+            monomial_order: self.monomial_order.clone(),
+            ring,
+        })
+    }
+
+    /// 積 (異なる環の多項式ならエラー)
+    pub fn try_mul(&self, other: &Polynomial<K>) -> Result<Polynomial<K>, RingError> {
+        let ring = ring::common_ring(self, other)?;
+
+        Ok(Polynomial {
+            terms: self.terms.mul(&other.terms),
+            n: self.n,
+            monomial_order: self.monomial_order.clone(),
+            ring,
+        })
+    }
+
+    /// self から c m g を引く (self の項の配列をそのまま使う)
+    pub fn sub_mul_term(&mut self, c: &K, m: &Monomial, g: &Polynomial<K>) {
+        self.ring = ring::common_ring(self, g).unwrap_or_else(|e| panic!("{}", e));
+        self.terms.sub_mul_term(c, m, &g.terms);
+    }
+
+    /// 単項式 m を掛ける
+    pub fn mul_monomial(&mut self, m: &Monomial) {
+        self.mul_term(&K::one(), m);
+    }
+
+    /// 項 c m を掛ける
+    pub fn mul_term(&mut self, c: &K, m: &Monomial) {
+        self.terms.mul_term_assign(c, m);
+    }
+
+    // 各変数の最大次数
+    fn max_degrees(&self) -> Vec<u64> {
+        let mut ret = vec![0; self.n];
+        for m in self.terms.keys() {
+            for (d, &e) in ret.iter_mut().zip(m.exponents()) {
+                *d = (*d).max(u64::from(e));
+            }
+        }
+        ret
+    }
 
-        for (k, v) in &shorter.terms {
-            assert_ne!(v, &Rational::from(0));
-            ret.add_term(v.clone(), k.clone());
+    /// 積 (指数が u32 に収まらなければ None)
+    pub fn checked_mul(&self, other: &Polynomial<K>) -> Option<Polynomial<K>> {
+        let (a, b) = (self.max_degrees(), other.max_degrees());
+        if a.iter().zip(&b).any(|(a, b)| a + b > u64::from(u32::MAX)) {
+            return None;
         }
+        Some(self * other)
+    }
 
+    /// e 乗 (指数が u32 に収まらなければ None)
+    pub fn checked_pow(&self, e: u32) -> Option<Polynomial<K>> {
+        if self
+            .max_degrees()
+            .iter()
+            .any(|d| d * u64::from(e) > u64::from(u32::MAX))
+        {
+            return None;
+        }
+        Some(self.pow(e))
+    }
+
+    /// e 乗 (繰り返し2乗法)
+    pub fn pow(&self, e: u32) -> Polynomial<K> {
+        let mut ret = Polynomial::new(self.n, self.monomial_order.clone());
+        ret.ring = self.ring.clone();
+        ret.add_term(
+            K::one(),
+            Monomial::from((self.n, self.monomial_order.clone())),
+        );
+
+        let mut base = self.clone();
+        let mut e = e;
+        while e > 0 {
+            if e & 1 == 1 {
+                ret = &ret * &base;
+            }
+            e >>= 1;
+            if e > 0 {
+                base = &base * &base;
+            }
+        }
         ret
     }
 }
 
-impl Add<&Polynomial> for Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Add<&Polynomial<K>> for &Polynomial<K> {
+    type Output = Polynomial<K>;
+
+    fn add(self, other: &Polynomial<K>) -> Polynomial<K> {
+        self.try_add(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<K: Ring> Add<&Polynomial<K>> for Polynomial<K> {
+    type Output = Polynomial<K>;
 
-    fn add(self, other: &Polynomial) -> Polynomial {
+    fn add(self, other: &Polynomial<K>) -> Polynomial<K> {
         &self + other
     }
 }
 
-impl Add<Polynomial> for &Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Add<Polynomial<K>> for &Polynomial<K> {
+    type Output = Polynomial<K>;
 
-    fn add(self, other: Polynomial) -> Polynomial {
+    fn add(self, other: Polynomial<K>) -> Polynomial<K> {
         self + &other
     }
 }
 
-impl Add<Polynomial> for Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Add<Polynomial<K>> for Polynomial<K> {
+    type Output = Polynomial<K>;
 
-    fn add(self, other: Polynomial) -> Polynomial {
+    fn add(self, other: Polynomial<K>) -> Polynomial<K> {
         &self + &other
     }
 }
 
-impl<'a, 'b> Sub<&'a Polynomial> for &'b Polynomial {
-    type Output = Polynomial;
-
-    fn sub(self, other: &Polynomial) -> Polynomial {
-        assert!(self.n == other.n);
-        assert!(self.monomial_order == other.monomial_order);
-
-        let mut ret = self.clone();
+impl<K: Ring> Sub<&Polynomial<K>> for &Polynomial<K> {
+    type Output = Polynomial<K>;
 
-        for (k, v) in &other.terms {
-            assert_ne!(v, &Rational::from(0));
-            ret.sub_term(v.clone(), k.clone());
-        }
-
-        ret
+    fn sub(self, other: &Polynomial<K>) -> Polynomial<K> {
+        self.try_sub(other).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
-impl Sub<&Polynomial> for Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Sub<&Polynomial<K>> for Polynomial<K> {
+    type Output = Polynomial<K>;
 
-    fn sub(self, other: &Polynomial) -> Polynomial {
+    fn sub(self, other: &Polynomial<K>) -> Polynomial<K> {
         &self - other
     }
 }
 
-impl Sub<Polynomial> for &Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Sub<Polynomial<K>> for &Polynomial<K> {
+    type Output = Polynomial<K>;
 
-    fn sub(self, other: Polynomial) -> Polynomial {
+    fn sub(self, other: Polynomial<K>) -> Polynomial<K> {
         self - &other
     }
 }
 
-impl Sub<Polynomial> for Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Sub<Polynomial<K>> for Polynomial<K> {
+    type Output = Polynomial<K>;
 
-    fn sub(self, other: Polynomial) -> Polynomial {
+    fn sub(self, other: Polynomial<K>) -> Polynomial<K> {
         &self - &other
     }
 }
 
-impl<'a, 'b> Mul<&'a Polynomial> for &'b Polynomial {
-    type Output = Polynomial;
-
-    fn mul(self, other: &Polynomial) -> Polynomial {
-        assert!(self.n == other.n);
-        assert!(self.monomial_order == other.monomial_order);
+impl<K: Ring> Mul<&Polynomial<K>> for &Polynomial<K> {
+    type Output = Polynomial<K>;
 
-        let mut ret = Polynomial {
-            terms: BTreeMap::new(),
-            n: self.n,
-            monomial_order: self.monomial_order,
-        };
-
-        for (lx, lc) in &self.terms {
-            assert_ne!(lc, &Rational::from(0));
-            for (rx, rc) in &other.terms {
-                assert_ne!(rc, &Rational::from(0));
-                ret.add_term(lc * rc, lx * rx);
-            }
-        }
-
-        ret
+    fn mul(self, other: &Polynomial<K>) -> Polynomial<K> {
+        self.try_mul(other).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
-impl Mul<&Polynomial> for Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Mul<&Polynomial<K>> for Polynomial<K> {
+    type Output = Polynomial<K>;
 
-    fn mul(self, other: &Polynomial) -> Polynomial {
+    fn mul(self, other: &Polynomial<K>) -> Polynomial<K> {
         &self * other
     }
 }
 
-impl Mul<Polynomial> for &Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Mul<Polynomial<K>> for &Polynomial<K> {
+    type Output = Polynomial<K>;
 
-    fn mul(self, other: Polynomial) -> Polynomial {
+    fn mul(self, other: Polynomial<K>) -> Polynomial<K> {
         self * &other
     }
 }
 
-impl Mul<Polynomial> for Polynomial {
-    type Output = Polynomial;
+impl<K: Ring> Mul<Polynomial<K>> for Polynomial<K> {
+    type Output = Polynomial<K>;
 
-    fn mul(self, other: Polynomial) -> Polynomial {
+    fn mul(self, other: Polynomial<K>) -> Polynomial<K> {
         &self * &other
     }
 }
 
-impl PartialEq for Polynomial {
+impl<K: Ring> AddAssign<&Polynomial<K>> for Polynomial<K> {
+    fn add_assign(&mut self, other: &Polynomial<K>) {
+        self.ring = ring::common_ring(self, other).unwrap_or_else(|e| panic!("{}", e));
+        self.terms.merge_assign(
+            other
+                .terms
+                .iter()
+                .map(|(m, c)| (m.clone(), c.clone()))
+                .collect(),
+        );
+    }
+}
+
+impl<K: Ring> AddAssign<Polynomial<K>> for Polynomial<K> {
+    fn add_assign(&mut self, other: Polynomial<K>) {
+        self.ring = ring::common_ring(self, &other).unwrap_or_else(|e| panic!("{}", e));
+        self.terms.merge_assign(other.terms.into_iter().collect());
+    }
+}
+
+impl<K: Ring> SubAssign<&Polynomial<K>> for Polynomial<K> {
+    fn sub_assign(&mut self, other: &Polynomial<K>) {
+        self.ring = ring::common_ring(self, other).unwrap_or_else(|e| panic!("{}", e));
+        self.terms.merge_assign(
+            other
+                .terms
+                .iter()
+                .map(|(m, c)| (m.clone(), -c.clone()))
+                .collect(),
+        );
+    }
+}
+
+impl<K: Ring> SubAssign<Polynomial<K>> for Polynomial<K> {
+    fn sub_assign(&mut self, other: Polynomial<K>) {
+        self.ring = ring::common_ring(self, &other).unwrap_or_else(|e| panic!("{}", e));
+        self.terms
+            .merge_assign(other.terms.into_iter().map(|(m, c)| (m, -c)).collect());
+    }
+}
+
+impl<K: Ring> MulAssign<&Polynomial<K>> for Polynomial<K> {
+    fn mul_assign(&mut self, other: &Polynomial<K>) {
+        *self = &*self * other;
+    }
+}
+
+impl<K: Ring> MulAssign<Polynomial<K>> for Polynomial<K> {
+    fn mul_assign(&mut self, other: Polynomial<K>) {
+        *self *= &other;
+    }
+}
+
+impl<K: Ring> Neg for &Polynomial<K> {
+    type Output = Polynomial<K>;
+
+    fn neg(self) -> Polynomial<K> {
+        self.map_coeff(|c| -c.clone())
+    }
+}
+
+impl<K: Ring> Neg for Polynomial<K> {
+    type Output = Polynomial<K>;
+
+    fn neg(self) -> Polynomial<K> {
+        -&self
+    }
+}
+
+// 係数環の元 (と整数) によるスカラー倍
+macro_rules! impl_scalar_mul {
+    ($k: ty, $s: ty, $conv: expr) => {
+        impl Mul<$s> for &Polynomial<$k> {
+            type Output = Polynomial<$k>;
+
+            fn mul(self, c: $s) -> Polynomial<$k> {
+                self.mul_coeff(&$conv(c))
+            }
+        }
+
+        impl Mul<$s> for Polynomial<$k> {
+            type Output = Polynomial<$k>;
+
+            fn mul(self, c: $s) -> Polynomial<$k> {
+                &self * c
+            }
+        }
+
+        impl MulAssign<$s> for Polynomial<$k> {
+            fn mul_assign(&mut self, c: $s) {
+                self.terms.scale_assign(&$conv(c));
+            }
+        }
+    };
+}
+
+impl_scalar_mul!(Rational, &Rational, Clone::clone);
+impl_scalar_mul!(Rational, &Integer, |c: &Integer| Rational::from(c.clone()));
+impl_scalar_mul!(Rational, i64, Rational::from);
+impl_scalar_mul!(Integer, &Integer, Clone::clone);
+impl_scalar_mul!(Integer, i64, Integer::from);
+
+impl<K: Ring> PartialEq for Polynomial<K> {
+    // 異なる環の多項式は等しくない
     fn eq(&self, other: &Self) -> bool {
-        assert_eq!(self.n, other.n);
-        assert_eq!(self.monomial_order, other.monomial_order);
-        self.terms.eq(&other.terms)
+        ring::common_ring(self, other).is_ok() && self.terms.eq(&other.terms)
     }
 }
 
-impl Eq for Polynomial {}
+impl<K: Ring> Eq for Polynomial<K> {}
 
-pub trait PolynomialHandlers {
+pub trait PolynomialHandlers<K: Ring> {
     fn add_monomial(&mut self, x: Monomial);
-    fn add_term(&mut self, c: Rational, x: Monomial);
+    fn add_term(&mut self, c: K, x: Monomial);
     fn sub_monomial(&mut self, x: Monomial);
-    fn sub_term(&mut self, c: Rational, x: Monomial);
-
-    fn polynomial_divide(&self, rhses: &Vec<Polynomial>) -> (Vec<Polynomial>, Polynomial);
-    fn polynomial_divide_ref(&self, rhses: &Vec<&Polynomial>) -> (Vec<Polynomial>, Polynomial);
+    fn sub_term(&mut self, c: K, x: Monomial);
+
+    fn polynomial_divide(&self, rhses: &[Polynomial<K>]) -> (Vec<Polynomial<K>>, Polynomial<K>)
+    where
+        K: Field;
+    fn polynomial_divide_ref(
+        &self,
+        rhses: &[&Polynomial<K>],
+    ) -> (Vec<Polynomial<K>>, Polynomial<K>)
+    where
+        K: Field;
 
     fn get_n(&self) -> usize;
     fn get_monomial_order(&self) -> MonomialOrder;
 
     fn set_monomial_order(&mut self, o: MonomialOrder);
 
-    fn fetch_lt(&self) -> Option<Polynomial>;
+    fn fetch_lt(&self) -> Option<Polynomial<K>>;
     fn fetch_lm(&self) -> Option<Monomial>;
-    fn fetch_lc(&self) -> Option<Rational>;
+    fn fetch_lc(&self) -> Option<K>;
 
     fn fetch_total_degree(&self) -> Option<Integer>;
 
-    fn normalize(self) -> Self;
-
-    fn integer_coeff(self) -> Self;
+    fn normalize(self) -> Self
+    where
+        K: Field;
 }
 
-impl PolynomialHandlers for Polynomial {
-    fn add_term(&mut self, c: Rational, x: Monomial) {
+impl<K: Ring> PolynomialHandlers<K> for Polynomial<K> {
+    fn add_term(&mut self, c: K, x: Monomial) {
         // fix monomial order
         let mut x = x;
-        x.set_monomial_order(self.monomial_order);
-
-        let res = self.terms.get_mut(&x);
-
-        let new_coeff = match res {
-            Some(c_) => c_.clone() + c,
-            None => c,
-        };
+        x.set_monomial_order(self.monomial_order.clone());
 
-        if new_coeff != Rational::zero() {
-            self.terms.insert(x, new_coeff);
-        } else {
-            self.terms.remove(&x);
-        }
+        self.terms.add_term(c, x);
     }
     fn add_monomial(&mut self, x: Monomial) {
-        self.add_term(Rational::from(1), x);
+        self.add_term(K::one(), x);
     }
 
-    fn sub_term(&mut self, c: Rational, x: Monomial) {
-        let res = self.terms.get_mut(&x);
-
-        let new_coeff = match res {
-            Some(c_) => c_.clone() - c,
-            None => -c,
-        };
-        if new_coeff != Rational::zero() {
-            self.terms.insert(x, new_coeff);
-        } else {
-            self.terms.remove(&x);
-        }
+    fn sub_term(&mut self, c: K, x: Monomial) {
+        self.add_term(-c, x);
     }
     fn sub_monomial(&mut self, x: Monomial) {
-        self.sub_term(Rational::from(1), x);
+        self.sub_term(K::one(), x);
     }
 
-    fn polynomial_divide(&self, rhses: &Vec<Polynomial>) -> (Vec<Polynomial>, Polynomial) {
-        self.polynomial_divide_ref(&rhses.iter().map(|f| f).collect::<Vec<&Polynomial>>())
+    fn polynomial_divide(&self, rhses: &[Polynomial<K>]) -> (Vec<Polynomial<K>>, Polynomial<K>)
+    where
+        K: Field,
+    {
+        self.polynomial_divide_ref(&rhses.iter().collect::<Vec<&Polynomial<K>>>())
     }
 
-    fn polynomial_divide_ref(&self, rhses: &Vec<&Polynomial>) -> (Vec<Polynomial>, Polynomial) {
-        let monomial_order = self.monomial_order;
-        let n = self.n;
-
-        let mut p = self.clone();
-        let zero = Polynomial::from((n, monomial_order));
-
-        let s = rhses.len();
+    fn polynomial_divide_ref(&self, rhses: &[&Polynomial<K>]) -> (Vec<Polynomial<K>>, Polynomial<K>)
+    where
+        K: Field,
+    {
+        // 商の項は降順に出てくる
+        let mut a = vec![Vec::new(); rhses.len()];
+        let r = self.divide_by(rhses, |i, m, c| a[i].push((m, c)));
 
-        let mut a = Vec::new();
-        a.resize(s, zero.clone());
-        let mut r = zero.clone();
-
-        while &p != &zero {
-            let mut divisionoccurred = false;
-
-            for i in 0..s {
-                let fi = rhses[i];
-                let lm_fi = fi.fetch_lm();
-                let lm_p = p.fetch_lm();
-
-                let lm_pair = (lm_p, lm_fi);
-
-                match lm_pair {
-                    (Some(lm_p), Some(lm_fi)) => {
-                        if lm_p.is_divisible_by(&lm_fi) {
-                            let lc_fi = fi.fetch_lc();
-                            let lc_p = p.fetch_lc();
-
-                            let lc_pair = (lc_p, lc_fi);
-                            match lc_pair {
-                                (Some(lc_p), Some(lc_fi)) => {
-                                    let d = Polynomial::from((
-                                        &lc_p / &lc_fi,
-                                        &lm_p / &lm_fi,
-                                        monomial_order,
-                                    ));
-                                    let ai = &a[i] + &d;
-                                    a[i] = ai;
-
-                                    p = &p - (&d * fi);
-                                }
-                                (_, _) => {
-                                    assert!(false);
-                                }
-                            }
-
-                            divisionoccurred = true;
-                            break;
-                        }
-                    }
-                    (_, _) => {
-                        panic!("found 0 polynomial\n p -> {:?}\n fi -> {:?},", p, fi);
-                    }
-                }
-            }
-            if !divisionoccurred {
-                let lt_p = p.fetch_lt();
-                match lt_p {
-                    Some(lt_p) => {
-                        r = &r + &lt_p;
-                        p = &p - &lt_p;
-                    }
-                    None => {
-                        assert!(false);
-                    }
+        let quotients = a
+            .into_iter()
+            .map(|mut terms| {
+                terms.reverse();
+                Polynomial {
+                    terms: Terms::from_sorted(terms),
+                    n: self.n,
+                    monomial_order: self.monomial_order.clone(),
+                    ring: self.ring.clone(),
                 }
-            }
-        }
-
-        (a, r)
+            })
+            .collect();
+        (quotients, r)
     }
 
     fn get_n(&self) -> usize {
@@ -448,40 +648,29 @@ impl PolynomialHandlers for Polynomial {
     }
 
     fn get_monomial_order(&self) -> MonomialOrder {
-        self.monomial_order
+        self.monomial_order.clone()
     }
 
     fn set_monomial_order(&mut self, o: MonomialOrder) {
-        self.monomial_order = o;
-        // TODO termsにも伝搬させる
-    }
-
-    fn fetch_lt(&self) -> Option<Polynomial> {
-        let last = self.terms.iter().last();
-        match last {
-            Some((monomial, coeff)) => Some(Polynomial::from((
-                coeff.clone(),
-                monomial.clone(),
-                self.monomial_order,
-            ))),
-            None => None,
-        }
+        let f = std::mem::replace(self, Polynomial::new(self.n, o.clone()));
+        *self = f.with_monomial_order(o);
+    }
+
+    fn fetch_lt(&self) -> Option<Polynomial<K>> {
+        self.terms.last().map(|(monomial, coeff)| {
+            let mut lt =
+                Polynomial::from((coeff.clone(), monomial.clone(), self.monomial_order.clone()));
+            lt.ring = self.ring.clone();
+            lt
+        })
     }
 
     fn fetch_lm(&self) -> Option<Monomial> {
-        let last = self.terms.iter().last();
-        match last {
-            Some((monomial, _)) => Some(monomial.clone()),
-            None => None,
-        }
+        self.terms.last().map(|(monomial, _)| monomial.clone())
     }
 
-    fn fetch_lc(&self) -> Option<Rational> {
-        let last = self.terms.iter().last();
-        match last {
-            Some((_, coeff)) => Some(coeff.clone()),
-            None => None,
-        }
+    fn fetch_lc(&self) -> Option<K> {
+        self.terms.last().map(|(_, coeff)| coeff.clone())
     }
 
     fn fetch_total_degree(&self) -> Option<Integer> {
@@ -490,62 +679,294 @@ impl PolynomialHandlers for Polynomial {
         } else {
             Some(
                 self.terms
-                    .iter()
-                    .map(|(x, _)| x.fetch_total_degree())
-                    .fold(Integer::from(0), |mx, d| std::cmp::max(mx, d)),
+                    .keys()
+                    .map(|x| x.fetch_total_degree())
+                    .fold(Integer::from(0), std::cmp::max),
             )
         }
     }
 
-    fn normalize(self) -> Self {
+    fn normalize(self) -> Self
+    where
+        K: Field,
+    {
         let mut f = self;
         let lc = f.fetch_lc();
         match lc {
             Some(lc) => {
-                f.terms = f
-                    .terms
-                    .iter()
-                    .map(|(monomial, coeff)| (monomial.clone(), coeff / &lc))
-                    .collect::<BTreeMap<Monomial, Rational>>();
+                let lc_inv = lc.inv();
+                f.terms = f.terms.map(|coeff| coeff.clone() * lc_inv.clone());
                 f
             }
             None => f,
         }
     }
-    fn integer_coeff(self) -> Self {
+}
+
+impl<K: Field> Polynomial<K> {
+    /// rhses で割った余り (商は作らない)
+    pub fn reduce(&self, rhses: &[&Polynomial<K>]) -> Polynomial<K> {
+        self.divide_by(rhses, |_, _, _| {})
+    }
+
+    // 割り算の余りを返し、商の項 c m を rhses[i] ごとに降順に quotient に渡す
+    fn divide_by(
+        &self,
+        rhses: &[&Polynomial<K>],
+        mut quotient: impl FnMut(usize, Monomial, K),
+    ) -> Polynomial<K> {
+        // 主項を除いた残りの部分を前もって作っておく
+        let divisors = rhses
+            .iter()
+            .map(|fi| {
+                ring::common_ring(self, fi).unwrap_or_else(|e| panic!("{}", e));
+                let mut tail = fi.terms.clone();
+                match tail.pop() {
+                    Some(lt) => (lt, tail),
+                    None => panic!("found 0 polynomial\n p -> {:?}\n fi -> {:?},", self, fi),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // p - d fi の計算で p 全体を作り直さないようにジオバケットに溜める
+        let mut p = Geobucket::new();
+        p.add(self.terms.clone());
+
+        // 余りの項は降順に出てくる
+        let mut r = Vec::new();
+
+        while let Some((lm_p, lc_p)) = p.pop_leading() {
+            let i = divisors
+                .iter()
+                .position(|((lm_fi, _), _)| lm_p.is_divisible_by(lm_fi));
+            match i {
+                Some(i) => {
+                    let ((lm_fi, lc_fi), tail) = &divisors[i];
+                    let c = lc_p / lc_fi.clone();
+                    let m = &lm_p / lm_fi;
+                    p.add(tail.mul_term(&-c.clone(), &m));
+                    quotient(i, m, c);
+                }
+                None => r.push((lm_p, lc_p)),
+            }
+        }
+
+        r.reverse();
+        Polynomial {
+            terms: Terms::from_sorted(r),
+            n: self.n,
+            monomial_order: self.monomial_order.clone(),
+            ring: self.ring.clone(),
+        }
+    }
+}
+
+impl Polynomial<Rational> {
+    /// 分母を払って整数係数にする
+    pub fn integer_coeff(self) -> Self {
         if self.terms.is_empty() {
             return self;
         }
-        let lcm_den =
-            self.terms
-                .iter()
-                .map(|(_, coeff)| coeff)
-                .fold(Integer::from(1), |lcm_den, a| {
-                    let den = a.get_den();
+        let lcm_den = self.terms.values().fold(Integer::from(1), |lcm_den, a| {
+            let den = a.get_den();
 
-                    scalar::lcm(&lcm_den, &den)
-                });
+            scalar::lcm(&lcm_den, &den)
+        });
 
         let lcm_den = Rational::from(lcm_den);
 
-        let it = self
-            .terms
-            .into_iter()
-            .map(|(term, coeff)| (term, coeff * &lcm_den));
+        let terms = self.terms.map(|coeff| coeff.clone() * &lcm_den);
 
-        let mut ret = Polynomial::from((self.n, self.monomial_order));
+        Self { terms, ..self }
+    }
+}
 
-        for pair in it {
-            ret.add_term(pair.1, pair.0);
+impl Polynomial<Rational> {
+    /// 分母を払い、内容で割った原始的な整数係数多項式
+    pub fn to_primitive_integer(&self) -> Polynomial<Integer> {
+        self.clone()
+            .integer_coeff()
+            .map_coeff(|c| c.get_num())
+            .primitive_part()
+    }
+}
+
+impl Polynomial<Integer> {
+    /// 係数の最大公約数 (0多項式では0)
+    pub fn content(&self) -> Integer {
+        self.terms
+            .values()
+            .fold(Integer::zero(), |g, c| scalar::gcd(&g, c).abs())
+    }
+
+    /// 内容で割り、主係数を正にしたもの
+    pub fn primitive_part(self) -> Self {
+        let content = self.content();
+        if content.is_zero() {
+            return self;
         }
+        let content = match self.fetch_lc() {
+            Some(lc) if lc < Integer::zero() => -content,
+            _ => content,
+        };
+        self.map_coeff(|c| c / &content)
+    }
 
-        ret
+    /// 分数を使わない簡約
+    ///
+    /// 主項が割り切れる項を見つけるたびに全体へ主係数を掛けてから引き、内容を取り除く。
+    /// 結果は Q 上の余りの定数倍で、原始的な多項式になる。
+    pub fn pseudo_reduce(&self, rhses: &[&Polynomial<Integer>]) -> Polynomial<Integer> {
+        let mut h = self.clone().primitive_part();
+        let mut cursor = h.fetch_lm();
+
+        while let Some(m) = cursor {
+            let divisor = rhses
+                .iter()
+                .find_map(|f| match (f.fetch_lm(), f.fetch_lc()) {
+                    (Some(lm_f), Some(lc_f)) if m.is_divisible_by(&lm_f) => Some((f, lm_f, lc_f)),
+                    (Some(_), Some(_)) => None,
+                    (_, _) => panic!("found 0 polynomial\n fi -> {:?},", f),
+                });
+
+            if let Some((f, lm_f, lc_f)) = divisor {
+                let c = h.terms.get(&m).unwrap().clone();
+                let g = scalar::gcd(&c, &lc_f).abs();
+
+                h *= &(&lc_f / &g);
+                h.sub_mul_term(&(&c / &g), &(&m / &lm_f), f);
+                h = h.primitive_part();
+            }
+
+            cursor = h.terms.prev(&m).cloned();
+        }
+
+        h
+    }
+
+    /// 係数も含めた簡約 (強グレブナー基底用)
+    ///
+    /// 項 c x は主項が x を割る g について c を lc(g) で割った商が0でない限り
+    /// c - q lc(g) (0 <= c - q lc(g) < |lc(g)|) に置き換えられる。
+    pub fn strong_normal_form(&self, rhses: &[&Polynomial<Integer>]) -> Polynomial<Integer> {
+        let mut h = self.clone();
+        let mut cursor = h.fetch_lm();
+
+        while let Some(m) = cursor {
+            let reducer = h.terms.get(&m).and_then(|c| {
+                rhses
+                    .iter()
+                    .find_map(|f| match (f.fetch_lm(), f.fetch_lc()) {
+                        (Some(lm_f), Some(lc_f)) => {
+                            let q = scalar::div_floor(c, &lc_f.abs());
+                            let q = if lc_f < Integer::zero() { -q } else { q };
+                            if !q.is_zero() && m.is_divisible_by(&lm_f) {
+                                Some((f, lm_f, q))
+                            } else {
+                                None
+                            }
+                        }
+                        (_, _) => panic!("found 0 polynomial\n fi -> {:?},", f),
+                    })
+            });
+
+            match reducer {
+                Some((f, lm_f, q)) => {
+                    // 係数が変わっただけなので同じ単項式をもう一度調べる
+                    h.sub_mul_term(&q, &(&m / &lm_f), f);
+                    cursor = Some(m);
+                }
+                None => {
+                    cursor = h.terms.prev(&m).cloned();
+                }
+            }
+        }
+
+        h
+    }
+}
+
+/// 多項式の並びの単項式順序をまとめて o に変える (同じ環の多項式は新しい環も共有する)
+pub fn with_monomial_order<K: Ring>(
+    fs: Vec<Polynomial<K>>,
+    o: &MonomialOrder,
+) -> Vec<Polynomial<K>> {
+    let mut rings: Vec<(Arc<PolynomialRing>, Arc<PolynomialRing>)> = Vec::new();
+    fs.into_iter()
+        .map(|f| {
+            let ring =
+                f.ring
+                    .as_ref()
+                    .map(|ring| match rings.iter().find(|(old, _)| old == ring) {
+                        Some((_, new)) => new.clone(),
+                        None => {
+                            let new = ring.with_monomial_order(o.clone());
+                            rings.push((ring.clone(), new.clone()));
+                            new
+                        }
+                    });
+            f.reorder(o.clone(), ring)
+        })
+        .collect()
+}
+
+/// 分数を使わない S 多項式 (Z 上の S 多項式でもある)
+pub fn s_polynomial_fraction_free(
+    f: &Polynomial<Integer>,
+    g: &Polynomial<Integer>,
+) -> Option<Polynomial<Integer>> {
+    // 異なる環の多項式なら panic する
+    ring::common_ring(f, g).unwrap_or_else(|e| panic!("{}", e));
+
+    match (f.fetch_lm(), g.fetch_lm(), f.fetch_lc(), g.fetch_lc()) {
+        (Some(lm_f), Some(lm_g), Some(lc_f), Some(lc_g)) => {
+            let lcm_fg = monomial::lcm(&lm_f, &lm_g);
+            let d = scalar::gcd(&lc_f, &lc_g).abs();
+
+            let mut a = f.clone();
+            a.mul_term(&(&lc_g / &d), &(&lcm_fg / &lm_f));
+            let mut b = g.clone();
+            b.mul_term(&(&lc_f / &d), &(&lcm_fg / &lm_g));
+
+            Some(&a - &b)
+        }
+        (_, _, _, _) => None,
+    }
+}
+
+/// GCD 多項式: d = u lc(f) + v lc(g) = gcd(lc(f), lc(g)) に対して
+/// u (lcm / lm(f)) f + v (lcm / lm(g)) g (主項は d lcm)
+pub fn g_polynomial(
+    f: &Polynomial<Integer>,
+    g: &Polynomial<Integer>,
+) -> Option<Polynomial<Integer>> {
+    // 異なる環の多項式なら panic する
+    ring::common_ring(f, g).unwrap_or_else(|e| panic!("{}", e));
+
+    match (f.fetch_lm(), g.fetch_lm(), f.fetch_lc(), g.fetch_lc()) {
+        (Some(lm_f), Some(lm_g), Some(lc_f), Some(lc_g)) => {
+            let lcm_fg = monomial::lcm(&lm_f, &lm_g);
+            let (d, u, v) = scalar::gcdext(&lc_f, &lc_g);
+            let (u, v) = if d < Integer::zero() {
+                (-u, -v)
+            } else {
+                (u, v)
+            };
+
+            let mut a = f.clone();
+            a.mul_term(&u, &(&lcm_fg / &lm_f));
+            let mut b = g.clone();
+            b.mul_term(&v, &(&lcm_fg / &lm_g));
+
+            Some(&a + &b)
+        }
+        (_, _, _, _) => None,
     }
 }
 
-pub fn s_polynomial(f: &Polynomial, g: &Polynomial) -> Option<Polynomial> {
-    assert_eq!(f.n, g.n);
-    assert_eq!(f.monomial_order, g.monomial_order);
+pub fn s_polynomial<K: Field>(f: &Polynomial<K>, g: &Polynomial<K>) -> Option<Polynomial<K>> {
+    // 異なる環の多項式なら panic する
+    ring::common_ring(f, g).unwrap_or_else(|e| panic!("{}", e));
     let lm_f = f.fetch_lm();
     let lm_g = g.fetch_lm();
 
@@ -557,10 +978,12 @@ pub fn s_polynomial(f: &Polynomial, g: &Polynomial) -> Option<Polynomial> {
             let lc_g = g.fetch_lc();
             match (lc_f, lc_g) {
                 (Some(lc_f), Some(lc_g)) => {
-                    let a = Polynomial::from((lc_f.invert(), &lcm_fg / &lm_f, f.monomial_order));
-                    let b = Polynomial::from((lc_g.invert(), &lcm_fg / &lm_g, g.monomial_order));
+                    let mut a = f.clone();
+                    a.mul_term(&lc_f.inv(), &(&lcm_fg / &lm_f));
+                    let mut b = g.clone();
+                    b.mul_term(&lc_g.inv(), &(&lcm_fg / &lm_g));
 
-                    Some(&a * f - &b * g)
+                    Some(&a - &b)
                 }
                 (_, _) => None,
             }
@@ -0,0 +1,132 @@
+use super::{Polynomial, PolynomialHandlers};
+use crate::monomial::Monomial;
+use crate::scalar::{Field, Integer};
+
+// ある変数 x_i についての1変数多項式と見た時の係数 (次数の昇順)
+type Univariate<K> = Vec<Polynomial<K>>;
+
+fn degree_in(m: &Monomial, i: usize) -> usize {
+    m.exponents()[i] as usize
+}
+
+fn to_univariate<K: Field>(f: &Polynomial<K>, i: usize) -> Univariate<K> {
+    let mut ret: Univariate<K> = Vec::new();
+    for (m, c) in f.terms() {
+        let d = degree_in(m, i);
+        if ret.len() <= d {
+            ret.resize(d + 1, Polynomial::new(f.n, f.monomial_order.clone()));
+        }
+
+        let mut v: Vec<Integer> = m.clone().into();
+        v[i] = Integer::zero();
+        ret[d].add_term(c.clone(), Monomial::from((v, f.monomial_order.clone())));
+    }
+    ret
+}
+
+fn from_univariate<K: Field>(f: &[Polynomial<K>], i: usize) -> Polynomial<K> {
+    let mut ret = Polynomial::new(f[0].n, f[0].monomial_order.clone());
+    for (d, c) in f.iter().enumerate() {
+        for (m, a) in c.terms() {
+            let mut v: Vec<Integer> = m.clone().into();
+            v[i] = Integer::from(d as u64);
+            ret.add_term(a.clone(), Monomial::from((v, f[0].monomial_order.clone())));
+        }
+    }
+    ret
+}
+
+fn trim<K: Field>(mut f: Univariate<K>) -> Univariate<K> {
+    while f.last().is_some_and(|c| c.terms.is_empty()) {
+        f.pop();
+    }
+    f
+}
+
+/// 割り切れることが分かっている時の商
+pub(crate) fn divide_exact<K: Field>(f: &Polynomial<K>, g: &Polynomial<K>) -> Polynomial<K> {
+    let (mut q, r) = f.polynomial_divide_ref(&[g]);
+    assert!(r.terms.is_empty(), "not divisible");
+    q.pop().unwrap()
+}
+
+// 係数 (x_i を含まない多項式) の gcd
+fn content<K: Field>(f: &[Polynomial<K>]) -> Polynomial<K> {
+    f.iter().fold(
+        Polynomial::new(f[0].n, f[0].monomial_order.clone()),
+        |g, c| gcd(&g, c),
+    )
+}
+
+fn primitive_part<K: Field>(f: Univariate<K>) -> Univariate<K> {
+    let c = content(&f);
+    f.iter().map(|a| divide_exact(a, &c)).collect()
+}
+
+// 擬剰余 lc(g)^(deg f - deg g + 1) f mod g
+fn pseudo_remainder<K: Field>(f: &[Polynomial<K>], g: &[Polynomial<K>]) -> Univariate<K> {
+    let lc_g = &g[g.len() - 1];
+    let mut r = f.to_vec();
+    while r.len() >= g.len() {
+        let shift = r.len() - g.len();
+        let lc_r = r[r.len() - 1].clone();
+        for a in &mut r {
+            *a *= lc_g;
+        }
+        for (j, b) in g.iter().enumerate() {
+            r[shift + j] -= &lc_r * b;
+        }
+        r = trim(r);
+    }
+    r
+}
+
+/// 多変数多項式の最大公約数 (主係数を1に正規化する, gcd(0, 0) = 0)
+///
+/// 添字の最も小さい変数についての原始的剰余列で求め、係数の内容は残りの変数について再帰的に求める。
+pub fn gcd<K: Field>(f: &Polynomial<K>, g: &Polynomial<K>) -> Polynomial<K> {
+    assert_eq!(f.n, g.n);
+    assert_eq!(f.monomial_order, g.monomial_order);
+
+    if f.terms.is_empty() {
+        return g.clone().normalize();
+    }
+    if g.terms.is_empty() {
+        return f.clone().normalize();
+    }
+
+    // どちらかに現れる添字の最も小さい変数
+    let i = (0..f.n).find(|&i| f.terms().chain(g.terms()).any(|(m, _)| degree_in(m, i) > 0));
+    let i = match i {
+        Some(i) => i,
+        // 共に定数
+        None => return Polynomial::from((K::one(), f.n, f.monomial_order.clone())),
+    };
+
+    let (f, g) = (to_univariate(f, i), to_univariate(g, i));
+    let c = gcd(&content(&f), &content(&g));
+
+    let (mut a, mut b) = (primitive_part(f), primitive_part(g));
+    if a.len() < b.len() {
+        std::mem::swap(&mut a, &mut b);
+    }
+    loop {
+        match b.len() {
+            0 => return (&c * &from_univariate(&a, i)).normalize(),
+            // 原始的な剰余列が x_i について定数になったら互いに素
+            1 => return c,
+            _ => {}
+        }
+
+        let r = pseudo_remainder(&a, &b);
+        a = b;
+        b = if r.is_empty() {
+            r
+        } else {
+            // 係数の膨張を抑えるため主係数の主係数を1にする
+            let r = primitive_part(r);
+            let lc_inv = r[r.len() - 1].fetch_lc().unwrap().inv();
+            r.iter().map(|a| a.mul_coeff(&lc_inv)).collect()
+        };
+    }
+}
@@ -0,0 +1,72 @@
+use crate::monomial::Monomial;
+use crate::polynomial::terms::Terms;
+use crate::scalar::Ring;
+
+/// ジオバケット (Yan, 1998)
+///
+/// 多項式を長さ 4^(i+1) 以下の項の列の和として持つ。短い列を足す時は小さいバケットとの
+/// 併合で済むので、割り算で除数の倍を何度も引く時の手間が除数の長さ程度になる。
+pub(crate) struct Geobucket<K: Ring> {
+    buckets: Vec<Terms<K>>,
+}
+
+// i 番目のバケットに入る項の数
+fn capacity(i: usize) -> usize {
+    4usize.saturating_pow(i as u32 + 1)
+}
+
+impl<K: Ring> Geobucket<K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            buckets: Vec::new(),
+        }
+    }
+
+    pub(crate) fn add(&mut self, terms: Terms<K>) {
+        if terms.is_empty() {
+            return;
+        }
+
+        let mut i = 0;
+        while capacity(i) < terms.len() {
+            i += 1;
+        }
+
+        let mut terms = terms;
+        loop {
+            if self.buckets.len() <= i {
+                self.buckets.resize_with(i + 1, Terms::new);
+            }
+            terms = self.buckets[i].merge(&terms, K::clone);
+            if terms.len() <= capacity(i) {
+                self.buckets[i] = terms;
+                return;
+            }
+            // 溢れたら次のバケットへ
+            self.buckets[i] = Terms::new();
+            i += 1;
+        }
+    }
+
+    /// 主項を取り出す (0 なら None)
+    pub(crate) fn pop_leading(&mut self) -> Option<(Monomial, K)> {
+        loop {
+            let m = self
+                .buckets
+                .iter()
+                .filter_map(|b| b.last().map(|(m, _)| m))
+                .max()?
+                .clone();
+
+            let mut c = K::zero();
+            for b in &mut self.buckets {
+                if b.last().is_some_and(|(x, _)| *x == m) {
+                    c = c + b.pop().unwrap().1;
+                }
+            }
+            if !c.is_zero() {
+                return Some((m, c));
+            }
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use crate::monomial::MatrixOrderError;
+use crate::polynomial::{Polynomial, PolynomialRing};
+use crate::scalar::{Field, Integer, Rational};
+
+use std::fmt::{Display, Error, Formatter};
+use std::sync::Arc;
+
+/// 多項式の式を読めなかった時のエラー (位置は入力の先頭からのバイト数)
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseError {
+    /// 使えない文字
+    UnexpectedChar(usize, char),
+    /// ここに来てはいけない字句
+    UnexpectedToken(usize, String),
+    /// 式が途中で終わった
+    UnexpectedEnd(usize),
+    /// 環に無い変数
+    UnknownVariable(usize, String),
+    /// 指数が非負整数でない (または指数が大きすぎる)
+    InvalidExponent(usize),
+    /// 0 か定数でない式で割った
+    InvalidDivisor(usize),
+    /// 単項式順序にならない重み行列
+    InvalidMonomialOrder(usize, MatrixOrderError),
+}
+
+impl ParseError {
+    pub fn position(&self) -> usize {
+        match self {
+            ParseError::UnexpectedChar(pos, _)
+            | ParseError::UnexpectedToken(pos, _)
+            | ParseError::UnexpectedEnd(pos)
+            | ParseError::UnknownVariable(pos, _)
+            | ParseError::InvalidExponent(pos)
+            | ParseError::InvalidDivisor(pos)
+            | ParseError::InvalidMonomialOrder(pos, _) => *pos,
+        }
+    }
+}
+
+impl ParseError {
+    // 位置を offset だけずらす
+    fn shift(self, offset: usize) -> Self {
+        match self {
+            ParseError::UnexpectedChar(pos, c) => ParseError::UnexpectedChar(pos + offset, c),
+            ParseError::UnexpectedToken(pos, s) => ParseError::UnexpectedToken(pos + offset, s),
+            ParseError::UnexpectedEnd(pos) => ParseError::UnexpectedEnd(pos + offset),
+            ParseError::UnknownVariable(pos, s) => ParseError::UnknownVariable(pos + offset, s),
+            ParseError::InvalidExponent(pos) => ParseError::InvalidExponent(pos + offset),
+            ParseError::InvalidDivisor(pos) => ParseError::InvalidDivisor(pos + offset),
+            ParseError::InvalidMonomialOrder(pos, e) => {
+                ParseError::InvalidMonomialOrder(pos + offset, e)
+            }
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            ParseError::UnexpectedChar(pos, c) => {
+                write!(f, "unexpected character '{}' at {}", c, pos)
+            }
+            ParseError::UnexpectedToken(pos, s) => write!(f, "unexpected '{}' at {}", s, pos),
+            ParseError::UnexpectedEnd(pos) => write!(f, "unexpected end of input at {}", pos),
+            ParseError::UnknownVariable(pos, s) => write!(f, "unknown variable '{}' at {}", s, pos),
+            ParseError::InvalidExponent(pos) => {
+                write!(
+                    f,
+                    "exponent must be a small enough non-negative integer at {}",
+                    pos
+                )
+            }
+            ParseError::InvalidDivisor(pos) => {
+                write!(f, "divisor must be a non-zero constant at {}", pos)
+            }
+            ParseError::InvalidMonomialOrder(pos, e) => write!(f, "{} at {}", e, pos),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Token {
+    Number,
+    Ident,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    LParen,
+    RParen,
+    // 以下は式のリストや関数呼び出し (Mathematica の入力など) 用
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Comma,
+    Arrow,
+}
+
+// (開始位置, 終了位置, 種類)
+fn tokenize(s: &str) -> Result<Vec<(usize, usize, Token)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => Token::Plus,
+            '-' if chars.next_if(|(_, c)| *c == '>').is_some() => Token::Arrow,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '^' => Token::Caret,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            '0'..='9' => {
+                while chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+                Token::Number
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while chars
+                    .next_if(|(_, c)| c.is_alphanumeric() || *c == '_')
+                    .is_some()
+                {}
+                Token::Ident
+            }
+            c => return Err(ParseError::UnexpectedChar(start, c)),
+        };
+        let end = chars.peek().map_or(s.len(), |(i, _)| *i);
+        tokens.push((start, end, token));
+    }
+
+    Ok(tokens)
+}
+
+// ±1 倍の単項式と 0 以外の冪の指数の上限
+const MAX_EXPONENT: u32 = 65535;
+
+// 再帰下降で読む
+//
+// expr  := term (('+' | '-') term)*
+// term  := unary (('*' | '/') unary | power)*   (並べただけの積も許す)
+// unary := ('+' | '-') unary | power
+// power := primary ('^' number)?
+// primary := number | ident | '(' expr ')'
+pub(crate) struct Parser<'a> {
+    ring: Option<Arc<PolynomialRing>>,
+    src: &'a str,
+    tokens: Vec<(usize, usize, Token)>,
+    pub(crate) cursor: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(src: &'a str) -> Result<Self, ParseError> {
+        Ok(Self {
+            ring: None,
+            src,
+            tokens: tokenize(src)?,
+            cursor: 0,
+        })
+    }
+
+    /// 変数はこの環のものとして読む
+    pub(crate) fn set_ring(&mut self, ring: &Arc<PolynomialRing>) {
+        self.ring = Some(ring.clone());
+    }
+
+    fn ring(&self) -> &Arc<PolynomialRing> {
+        self.ring.as_ref().expect("polynomial ring is not set")
+    }
+
+    pub(crate) fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.cursor).map(|(_, _, token)| *token)
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.tokens
+            .get(self.cursor)
+            .map_or(self.src.len(), |(start, _, _)| *start)
+    }
+
+    pub(crate) fn text(&self) -> &'a str {
+        let (start, end, _) = self.tokens[self.cursor];
+        &self.src[start..end]
+    }
+
+    pub(crate) fn unexpected(&self) -> ParseError {
+        match self.peek() {
+            Some(_) => ParseError::UnexpectedToken(self.position(), self.text().to_string()),
+            None => ParseError::UnexpectedEnd(self.position()),
+        }
+    }
+
+    /// 次の字句が token なら読み進める
+    pub(crate) fn expect(&mut self, token: Token) -> Result<(), ParseError> {
+        if self.peek() != Some(token) {
+            return Err(self.unexpected());
+        }
+        self.cursor += 1;
+        Ok(())
+    }
+
+    /// 入力を読み終えたか確かめる
+    pub(crate) fn finish(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(_) => Err(self.unexpected()),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn expr(&mut self) -> Result<Polynomial, ParseError> {
+        let mut ret = self.term()?;
+        loop {
+            match self.peek() {
+                Some(Token::Plus) => {
+                    self.cursor += 1;
+                    ret = &ret + &self.term()?;
+                }
+                Some(Token::Minus) => {
+                    self.cursor += 1;
+                    ret = &ret - &self.term()?;
+                }
+                _ => return Ok(ret),
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Polynomial, ParseError> {
+        let mut ret = self.unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Star) => {
+                    self.cursor += 1;
+                    let position = self.position();
+                    let rhs = self.unary()?;
+                    ret = ret
+                        .checked_mul(&rhs)
+                        .ok_or(ParseError::InvalidExponent(position))?;
+                }
+                Some(Token::Slash) => {
+                    self.cursor += 1;
+                    let position = self.position();
+                    let d = self.unary()?;
+                    let c = match d.terms().next() {
+                        Some((m, c)) if d.terms.len() == 1 && m.total_degree() == 0 => c.clone(),
+                        _ => return Err(ParseError::InvalidDivisor(position)),
+                    };
+                    ret = ret.mul_coeff(&c.inv());
+                }
+                Some(Token::Number) | Some(Token::Ident) | Some(Token::LParen) => {
+                    let position = self.position();
+                    let rhs = self.power()?;
+                    ret = ret
+                        .checked_mul(&rhs)
+                        .ok_or(ParseError::InvalidExponent(position))?;
+                }
+                _ => return Ok(ret),
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Polynomial, ParseError> {
+        match self.peek() {
+            Some(Token::Plus) => {
+                self.cursor += 1;
+                self.unary()
+            }
+            Some(Token::Minus) => {
+                self.cursor += 1;
+                Ok(-self.unary()?)
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<Polynomial, ParseError> {
+        let base = self.primary()?;
+        if self.peek() != Some(Token::Caret) {
+            return Ok(base);
+        }
+        self.cursor += 1;
+
+        let position = self.position();
+        if self.peek() != Some(Token::Number) {
+            return Err(ParseError::InvalidExponent(position));
+        }
+        let e = self
+            .text()
+            .parse::<u32>()
+            .map_err(|_| ParseError::InvalidExponent(position))?;
+        self.cursor += 1;
+
+        // ±1 倍の単項式と 0 以外の大きな冪は係数や項が巨大になって終わらないので制限する
+        let monomial = base.terms.len() <= 1
+            && base
+                .terms()
+                .all(|(_, c)| *c == Rational::from(1) || *c == Rational::from(-1));
+        if e > MAX_EXPONENT && !monomial {
+            return Err(ParseError::InvalidExponent(position));
+        }
+        base.checked_pow(e)
+            .ok_or(ParseError::InvalidExponent(position))
+    }
+
+    fn primary(&mut self) -> Result<Polynomial, ParseError> {
+        match self.peek() {
+            Some(Token::Number) => {
+                let c = match self.text().parse::<Integer>() {
+                    Ok(c) => c,
+                    Err(_) => unreachable!(),
+                };
+                self.cursor += 1;
+                Ok(self.ring().constant(Rational::from(c)))
+            }
+            Some(Token::Ident) => {
+                let name = self.text();
+                if self.ring().index(name).is_none() {
+                    return Err(ParseError::UnknownVariable(
+                        self.position(),
+                        name.to_string(),
+                    ));
+                }
+                self.cursor += 1;
+                Ok(self.ring().var(name))
+            }
+            Some(Token::LParen) => {
+                self.cursor += 1;
+                let ret = self.expr()?;
+                self.expect(Token::RParen)?;
+                Ok(ret)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+pub(crate) fn parse(ring: &Arc<PolynomialRing>, s: &str) -> Result<Polynomial, ParseError> {
+    let mut parser = Parser::new(s)?;
+    parser.set_ring(ring);
+
+    let ret = parser.expr()?;
+    parser.finish()?;
+
+    Ok(ret)
+}
+
+/// 入力全体の offset バイト目から始まる部分 s を読む (エラーの位置は入力全体での位置)
+pub(crate) fn parse_at(
+    ring: &Arc<PolynomialRing>,
+    s: &str,
+    offset: usize,
+) -> Result<Polynomial, ParseError> {
+    parse(ring, s).map_err(|e| e.shift(offset))
+}
@@ -0,0 +1,170 @@
+use crate::monomial::{Monomial, MonomialOrder};
+use crate::polynomial::{parser, ParseError, Polynomial, PolynomialHandlers};
+use crate::scalar::Ring;
+
+use std::fmt::{Display, Error, Formatter};
+use std::sync::Arc;
+
+/// 多項式環 K[x_1, ..., x_n]
+///
+/// 変数名と単項式順序を持ち、この環から作った多項式の間で共有する。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PolynomialRing {
+    names: Vec<String>,
+    monomial_order: MonomialOrder,
+}
+
+// 変数の数が決まっている順序はその数が環の変数の数と同じでなければならない
+fn check_monomial_order(monomial_order: &MonomialOrder, n: usize) {
+    assert!(
+        monomial_order.n_variables().is_none_or(|m| m == n),
+        "monomial order {} is not on {} variables",
+        monomial_order,
+        n
+    );
+}
+
+impl PolynomialRing {
+    pub fn new(names: &[&str], monomial_order: MonomialOrder) -> Arc<Self> {
+        for (i, name) in names.iter().enumerate() {
+            assert!(!name.is_empty(), "empty variable name");
+            assert!(
+                !names[..i].contains(name),
+                "duplicate variable name: {}",
+                name
+            );
+        }
+        check_monomial_order(&monomial_order, names.len());
+        Arc::new(Self {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            monomial_order,
+        })
+    }
+
+    pub fn get_n(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn get_monomial_order(&self) -> MonomialOrder {
+        self.monomial_order.clone()
+    }
+
+    /// 変数名が同じで単項式順序が o の環
+    pub fn with_monomial_order(&self, o: MonomialOrder) -> Arc<Self> {
+        check_monomial_order(&o, self.names.len());
+        Arc::new(Self {
+            names: self.names.clone(),
+            monomial_order: o,
+        })
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// 変数の添字 (0始まり)
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|x| x == name)
+    }
+
+    pub fn monomial(&self, exponents: Vec<i64>) -> Monomial {
+        assert_eq!(exponents.len(), self.get_n());
+        Monomial::from((exponents, self.get_monomial_order()))
+    }
+
+    pub fn zero<K: Ring>(self: &Arc<Self>) -> Polynomial<K> {
+        let mut ret = Polynomial::new(self.get_n(), self.get_monomial_order());
+        ret.ring = Some(self.clone());
+        ret
+    }
+
+    /// c x^exponents
+    pub fn term<K: Ring>(self: &Arc<Self>, c: K, exponents: Vec<i64>) -> Polynomial<K> {
+        let mut ret = self.zero();
+        ret.add_term(c, self.monomial(exponents));
+        ret
+    }
+
+    pub fn constant<K: Ring>(self: &Arc<Self>, c: K) -> Polynomial<K> {
+        self.term(c, vec![0; self.get_n()])
+    }
+
+    /// 名前が name の変数
+    pub fn var<K: Ring>(self: &Arc<Self>, name: &str) -> Polynomial<K> {
+        let i = self
+            .index(name)
+            .unwrap_or_else(|| panic!("no such variable: {}", name));
+        let mut v = vec![0; self.get_n()];
+        v[i] = 1;
+        self.term(K::one(), v)
+    }
+
+    /// 全ての変数
+    pub fn vars<K: Ring>(self: &Arc<Self>) -> Vec<Polynomial<K>> {
+        self.names.iter().map(|name| self.var(name)).collect()
+    }
+
+    /// "a1^2 - 2*a1*b1 + (c1 - b1)^2 - 3/4" のような式を読む
+    ///
+    /// +, -, *, 非負整数の指数の ^, 括弧, 有理数の定数が使える。
+    /// 2 a1 b1 のように並べただけでも積になる。
+    pub fn parse(self: &Arc<Self>, s: &str) -> Result<Polynomial, ParseError> {
+        parser::parse(self, s)
+    }
+}
+
+impl Display for PolynomialRing {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "[{}] {}", self.names.join(", "), self.monomial_order)
+    }
+}
+
+/// 異なる多項式環の多項式を混ぜた時のエラー
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RingError {
+    /// 変数の数が異なる
+    NumberOfVariables(usize, usize),
+    /// 単項式順序が異なる
+    MonomialOrder(String, String),
+    /// 変数名が異なる
+    Rings(String, String),
+}
+
+impl Display for RingError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            RingError::NumberOfVariables(l, r) => {
+                write!(f, "different numbers of variables: {} and {}", l, r)
+            }
+            RingError::MonomialOrder(l, r) => {
+                write!(f, "different monomial orders: {} and {}", l, r)
+            }
+            RingError::Rings(l, r) => write!(f, "different polynomial rings: {} and {}", l, r),
+        }
+    }
+}
+
+impl std::error::Error for RingError {}
+
+/// 2つの多項式に共通の環 (環を持たない多項式は相手の環に合わせる)
+pub(crate) fn common_ring<K: Ring>(
+    lhs: &Polynomial<K>,
+    rhs: &Polynomial<K>,
+) -> Result<Option<Arc<PolynomialRing>>, RingError> {
+    if lhs.n != rhs.n {
+        return Err(RingError::NumberOfVariables(lhs.n, rhs.n));
+    }
+    if lhs.monomial_order != rhs.monomial_order {
+        return Err(RingError::MonomialOrder(
+            lhs.monomial_order.to_string(),
+            rhs.monomial_order.to_string(),
+        ));
+    }
+    match (&lhs.ring, &rhs.ring) {
+        (Some(l), Some(r)) if !Arc::ptr_eq(l, r) && l != r => {
+            Err(RingError::Rings(l.to_string(), r.to_string()))
+        }
+        (Some(l), _) => Ok(Some(l.clone())),
+        (None, r) => Ok(r.clone()),
+    }
+}
@@ -0,0 +1,308 @@
+use crate::monomial::Monomial;
+use crate::scalar::Ring;
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::iter::FromIterator;
+
+/// 多項式の項の列
+///
+/// (単項式, 係数) を単項式順序の昇順に並べた連続した配列で持つ。係数は0でない。
+/// 主項は末尾にあるので O(1) で取れ、和と差は2つの列の併合で線形時間で求まる。
+#[derive(Clone, Debug)]
+pub(crate) struct Terms<K: Ring> {
+    terms: Vec<(Monomial, K)>,
+}
+
+impl<K: Ring> Terms<K> {
+    pub(crate) fn new() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    /// 昇順に並んだ係数0でない項から作る
+    pub(crate) fn from_sorted(terms: Vec<(Monomial, K)>) -> Self {
+        debug_assert!(terms.windows(2).all(|w| w[0].0 < w[1].0));
+        debug_assert!(terms.iter().all(|(_, c)| !c.is_zero()));
+        Self { terms }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = (&Monomial, &K)> {
+        self.terms.iter().map(|(m, c)| (m, c))
+    }
+
+    pub(crate) fn keys(&self) -> impl DoubleEndedIterator<Item = &Monomial> {
+        self.terms.iter().map(|(m, _)| m)
+    }
+
+    pub(crate) fn values(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.terms.iter().map(|(_, c)| c)
+    }
+
+    /// 主項 (最大の項)
+    pub(crate) fn last(&self) -> Option<(&Monomial, &K)> {
+        self.terms.last().map(|(m, c)| (m, c))
+    }
+
+    /// 主項を取り除く
+    pub(crate) fn pop(&mut self) -> Option<(Monomial, K)> {
+        self.terms.pop()
+    }
+
+    pub(crate) fn get(&self, m: &Monomial) -> Option<&K> {
+        self.search(m).ok().map(|i| &self.terms[i].1)
+    }
+
+    /// m より小さい最大の単項式
+    pub(crate) fn prev(&self, m: &Monomial) -> Option<&Monomial> {
+        let i = match self.search(m) {
+            Ok(i) | Err(i) => i,
+        };
+        i.checked_sub(1).map(|i| &self.terms[i].0)
+    }
+
+    /// c x を足す (0になった項は取り除く)
+    pub(crate) fn add_term(&mut self, c: K, x: Monomial) {
+        match self.search(&x) {
+            Ok(i) => {
+                let coeff = self.terms[i].1.clone() + c;
+                if coeff.is_zero() {
+                    self.terms.remove(i);
+                } else {
+                    self.terms[i].1 = coeff;
+                }
+            }
+            Err(i) => {
+                if !c.is_zero() {
+                    self.terms.insert(i, (x, c));
+                }
+            }
+        }
+    }
+
+    /// 係数を写像する (0になった項は落とす)
+    pub(crate) fn map<L: Ring>(&self, mut f: impl FnMut(&K) -> L) -> Terms<L> {
+        self.try_map::<L, (), _>(|c| Ok(f(c)))
+            .unwrap_or_else(|_| unreachable!())
+    }
+
+    pub(crate) fn try_map<L: Ring, E, F: FnMut(&K) -> Result<L, E>>(
+        &self,
+        mut f: F,
+    ) -> Result<Terms<L>, E> {
+        let mut terms = Vec::with_capacity(self.terms.len());
+        for (m, c) in &self.terms {
+            let c = f(c)?;
+            if !c.is_zero() {
+                terms.push((m.clone(), c));
+            }
+        }
+        Ok(Terms { terms })
+    }
+
+    /// c m を掛ける (単項式順序は積で保たれるので並べ直さなくてよい)
+    pub(crate) fn mul_term(&self, c: &K, m: &Monomial) -> Terms<K> {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(x, d)| (x * m, d.clone() * c.clone()))
+            .filter(|(_, d)| !d.is_zero())
+            .collect();
+        Terms { terms }
+    }
+
+    /// c m を掛ける (その場で)
+    pub(crate) fn mul_term_assign(&mut self, c: &K, m: &Monomial) {
+        for (x, d) in &mut self.terms {
+            *x = &*x * m;
+            *d = d.clone() * c.clone();
+        }
+        self.terms.retain(|(_, d)| !d.is_zero());
+    }
+
+    /// 全ての係数に c を掛ける
+    pub(crate) fn scale_assign(&mut self, c: &K) {
+        if *c == K::one() {
+            return;
+        }
+        for (_, d) in self.terms.iter_mut() {
+            *d = d.clone() * c.clone();
+        }
+        self.terms.retain(|(_, d)| !d.is_zero());
+    }
+
+    /// 積をヒープで求める (Monagan, Pearce)
+    ///
+    /// 項の少ない方の各項 f_i について f_i g_j の j を1つずつ進める列をヒープで併合する。
+    /// 積の項は昇順に出てくるので、同じ単項式はその場で足し合わせられる。
+    pub(crate) fn mul(&self, other: &Terms<K>) -> Terms<K> {
+        let (f, g) = if self.len() <= other.len() {
+            (&self.terms, &other.terms)
+        } else {
+            (&other.terms, &self.terms)
+        };
+        if f.is_empty() {
+            return Terms::new();
+        }
+
+        // (f_i g_j, i, j) の最小値を取り出す。i が小さいものから順に列をヒープへ入れる
+        let mut heap = BinaryHeap::with_capacity(f.len());
+        heap.push(Reverse((&f[0].0 * &g[0].0, 0, 0)));
+        let mut next = 1;
+
+        let mut terms: Vec<(Monomial, K)> = Vec::with_capacity(f.len() + g.len());
+        let mut current: Option<(Monomial, K)> = None;
+        while let Some(Reverse((m, i, j))) = heap.pop() {
+            let c = f[i].1.clone() * g[j].1.clone();
+            current = match current {
+                Some((x, d)) if x == m => Some((x, d + c)),
+                Some((x, d)) => {
+                    if !d.is_zero() {
+                        terms.push((x, d));
+                    }
+                    Some((m, c))
+                }
+                None => Some((m, c)),
+            };
+
+            // f_{i+1} g_0 は f_i g_0 より大きいので、f_i g_0 を取り出してから入れればよい
+            if j == 0 && next < f.len() {
+                heap.push(Reverse((&f[next].0 * &g[0].0, next, 0)));
+                next += 1;
+            }
+            if j + 1 < g.len() {
+                heap.push(Reverse((&f[i].0 * &g[j + 1].0, i, j + 1)));
+            }
+        }
+        if let Some((x, d)) = current {
+            if !d.is_zero() {
+                terms.push((x, d));
+            }
+        }
+
+        Terms { terms }
+    }
+
+    /// self + sign * other を併合で求める (sign は係数に掛ける関数)
+    pub(crate) fn merge(&self, other: &Terms<K>, sign: impl Fn(&K) -> K) -> Terms<K> {
+        let mut terms = Vec::with_capacity(self.terms.len() + other.terms.len());
+        let mut lhs = self.terms.iter().peekable();
+        let mut rhs = other.terms.iter().peekable();
+
+        loop {
+            let ord = match (lhs.peek(), rhs.peek()) {
+                (Some((l, _)), Some((r, _))) => l.cmp(r),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match ord {
+                Ordering::Less => terms.extend(lhs.next().cloned()),
+                Ordering::Greater => {
+                    let (m, c) = rhs.next().unwrap();
+                    terms.push((m.clone(), sign(c)));
+                }
+                Ordering::Equal => {
+                    let (m, a) = lhs.next().unwrap();
+                    let (_, b) = rhs.next().unwrap();
+                    let c = a.clone() + sign(b);
+                    if !c.is_zero() {
+                        terms.push((m.clone(), c));
+                    }
+                }
+            }
+        }
+
+        Terms { terms }
+    }
+
+    /// self - c m g をその場で求める (self の配列に後ろから併合する)
+    pub(crate) fn sub_mul_term(&mut self, c: &K, m: &Monomial, g: &Terms<K>) {
+        let c = -c.clone();
+        self.merge_assign(
+            g.terms
+                .iter()
+                .map(|(x, d)| (x * m, d.clone() * c.clone()))
+                .collect(),
+        );
+    }
+
+    /// 昇順に並んだ項の列 other を足す
+    ///
+    /// 配列を other の長さだけ伸ばし、大きい項から末尾へ詰めていくので self の項は作り直さない。
+    pub(crate) fn merge_assign(&mut self, mut other: Vec<(Monomial, K)>) {
+        let filler = match other.first() {
+            Some((m, _)) => (m.clone(), K::zero()),
+            None => return,
+        };
+        self.terms.resize(self.terms.len() + other.len(), filler);
+
+        // [0, i) はまだ見ていない self の項、[w, len) は併合した結果
+        let mut i = self.terms.len() - other.len();
+        let mut w = self.terms.len();
+        while let Some((m, c)) = other.pop() {
+            while i > 0 && self.terms[i - 1].0 > m {
+                i -= 1;
+                w -= 1;
+                self.terms.swap(i, w);
+            }
+            let c = if i > 0 && self.terms[i - 1].0 == m {
+                i -= 1;
+                self.terms[i].1.clone() + c
+            } else {
+                c
+            };
+            if !c.is_zero() {
+                w -= 1;
+                self.terms[w] = (m, c);
+            }
+        }
+
+        // 打ち消し合って空いた所を詰める
+        self.terms.drain(i..w);
+    }
+
+    fn search(&self, m: &Monomial) -> Result<usize, usize> {
+        self.terms.binary_search_by(|(x, _)| x.cmp(m))
+    }
+}
+
+impl<K: Ring> PartialEq for Terms<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.terms == other.terms
+    }
+}
+
+impl<K: Ring> IntoIterator for Terms<K> {
+    type Item = (Monomial, K);
+    type IntoIter = std::vec::IntoIter<(Monomial, K)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.terms.into_iter()
+    }
+}
+
+// 並び順は問わない (同じ単項式は足し合わせる)
+impl<K: Ring> FromIterator<(Monomial, K)> for Terms<K> {
+    fn from_iter<I: IntoIterator<Item = (Monomial, K)>>(iter: I) -> Self {
+        let mut terms = iter.into_iter().collect::<Vec<_>>();
+        terms.sort_by(|(l, _), (r, _)| l.cmp(r));
+
+        let mut ret: Vec<(Monomial, K)> = Vec::with_capacity(terms.len());
+        for (m, c) in terms {
+            match ret.last_mut() {
+                Some((x, d)) if *x == m => *d = d.clone() + c,
+                _ => ret.push((m, c)),
+            }
+        }
+        ret.retain(|(_, c)| !c.is_zero());
+        Terms { terms: ret }
+    }
+}
@@ -1,12 +1,15 @@
 #[cfg(test)]
 #[allow(unused_imports)]
-use super::{s_polynomial, Polynomial, PolynomialHandlers};
+use super::{
+    gcd, s_polynomial, with_monomial_order, ParseError, Polynomial, PolynomialHandlers,
+    PolynomialRing, RingError,
+};
 #[allow(unused_imports)]
 use crate::monomial;
 #[allow(unused_imports)]
-use crate::monomial::{Monomial, MonomialHandlers};
+use crate::monomial::{Monomial, MonomialHandlers, MonomialOrder};
 #[allow(unused_imports)]
-use crate::scalar::{Integer, Rational};
+use crate::scalar::{Integer, Rational, Ring};
 
 #[test]
 fn test_polynomial_divide() {
@@ -44,7 +47,7 @@ fn test_polynomial_divide() {
         Monomial::from(vec![Integer::from(0), Integer::from(0)]),
     );
 
-    let (a, r) = f.polynomial_divide(&vec![f0.clone(), f1.clone()]);
+    let (a, r) = f.polynomial_divide(&[f0.clone(), f1.clone()]);
 
     let correct = &a[0] * &f0 + &a[1] * &f1 + &r;
     assert_eq!(f, correct);
@@ -186,3 +189,448 @@ fn test_normalize() {
 
     assert_eq!(f, correct);
 }
+
+#[test]
+fn test_normalize_fp() {
+    // Q 以外の体 GF(7) 上の多項式
+    type F = crate::scalar::Fp<7>;
+
+    let mut f = Polynomial::<F>::new(2, MonomialOrder::Grlex);
+    f.add_term(F::from(3u64), Monomial::from(vec![2, 1]));
+    f.add_term(F::from(5u64), Monomial::from(vec![1, 0]));
+    f.add_term(F::from(1u64), Monomial::from(vec![0, 0]));
+
+    // 3^{-1} = 5 (mod 7)
+    let mut correct = Polynomial::<F>::new(2, MonomialOrder::Grlex);
+    correct.add_term(F::from(1u64), Monomial::from(vec![2, 1]));
+    correct.add_term(F::from(4u64), Monomial::from(vec![1, 0]));
+    correct.add_term(F::from(5u64), Monomial::from(vec![0, 0]));
+    assert_eq!(f.clone().normalize(), correct);
+
+    // 割り算も体の演算だけで行う
+    let mut g = Polynomial::<F>::new(2, MonomialOrder::Grlex);
+    g.add_term(F::from(2u64), Monomial::from(vec![1, 0]));
+    g.add_term(F::from(1u64), Monomial::from(vec![0, 0]));
+    let (a, r) = f.polynomial_divide(&[g.clone()]);
+    assert_eq!(&(&a[0] * &g) + &r, f);
+    assert!(r.terms().all(|(m, _)| m.exponents()[0] == 0));
+}
+
+#[test]
+fn test_primitive_part() {
+    let mut f = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    f.add_term(Integer::from(-6), Monomial::from(vec![2, 0]));
+    f.add_term(Integer::from(4), Monomial::from(vec![1, 1]));
+    f.add_term(Integer::from(10), Monomial::from(vec![0, 0]));
+
+    assert_eq!(f.content(), Integer::from(2));
+
+    let mut correct = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    correct.add_term(Integer::from(3), Monomial::from(vec![2, 0]));
+    correct.add_term(Integer::from(-2), Monomial::from(vec![1, 1]));
+    correct.add_term(Integer::from(-5), Monomial::from(vec![0, 0]));
+
+    assert_eq!(f.primitive_part(), correct);
+
+    // 3/4 x^2 - 1/6 y
+    let mut g = Polynomial::from((2, monomial::MonomialOrder::Lex));
+    g.add_term(
+        Rational::from(3) / Rational::from(4),
+        Monomial::from(vec![2, 0]),
+    );
+    g.add_term(
+        Rational::from(-1) / Rational::from(6),
+        Monomial::from(vec![0, 1]),
+    );
+
+    let mut correct = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    correct.add_term(Integer::from(9), Monomial::from(vec![2, 0]));
+    correct.add_term(Integer::from(-2), Monomial::from(vec![0, 1]));
+
+    assert_eq!(g.to_primitive_integer(), correct);
+}
+
+#[test]
+fn test_pseudo_reduce() {
+    // f = x^2 y + x y^2 + y^2 を 2y^2 - 2, 3xy - 3 で擬簡約する
+    let mut f = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    f.add_term(Integer::from(1), Monomial::from(vec![2, 1]));
+    f.add_term(Integer::from(1), Monomial::from(vec![1, 2]));
+    f.add_term(Integer::from(1), Monomial::from(vec![0, 2]));
+
+    let mut f0 = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    f0.add_term(Integer::from(2), Monomial::from(vec![0, 2]));
+    f0.add_term(Integer::from(-2), Monomial::from(vec![0, 0]));
+
+    let mut f1 = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    f1.add_term(Integer::from(3), Monomial::from(vec![1, 1]));
+    f1.add_term(Integer::from(-3), Monomial::from(vec![0, 0]));
+
+    let r = f.pseudo_reduce(&[&f0, &f1]);
+
+    // Q 上の余り 2x + 1 の原始的な定数倍
+    let mut correct = Polynomial::<Integer>::new(2, monomial::MonomialOrder::Lex);
+    correct.add_term(Integer::from(2), Monomial::from(vec![1, 0]));
+    correct.add_term(Integer::from(1), Monomial::from(vec![0, 0]));
+
+    assert_eq!(r, correct);
+}
+
+#[test]
+fn test_gcd() {
+    let term = |c: i64, v: Vec<i64>| {
+        Polynomial::from((
+            Rational::from(c),
+            Monomial::from(v),
+            monomial::MonomialOrder::Lex,
+        ))
+    };
+
+    // x y + z, z + 2
+    let common = &(&term(1, vec![1, 1, 0]) + &term(1, vec![0, 0, 1]))
+        * &(&term(1, vec![0, 0, 1]) + &term(2, vec![0, 0, 0]));
+
+    // x - y^2 + 1, 3 x + 3 z
+    let f =
+        &common * &(&(&term(1, vec![1, 0, 0]) - &term(1, vec![0, 2, 0])) + &term(1, vec![0, 0, 0]));
+    let g = &common * &(&term(3, vec![1, 0, 0]) + &term(3, vec![0, 0, 1]));
+
+    assert_eq!(gcd(&f, &g), common);
+    assert_eq!(gcd(&g, &f), common);
+    assert_eq!(gcd(&f, &term(0, vec![0, 0, 0])), f.clone().normalize());
+
+    // 互いに素
+    let h = &term(1, vec![0, 1, 0]) - &term(1, vec![0, 0, 0]);
+    assert_eq!(gcd(&f, &h), term(1, vec![0, 0, 0]));
+    assert_eq!(
+        gcd(&term(6, vec![0, 0, 0]), &term(4, vec![0, 0, 0])),
+        term(1, vec![0, 0, 0])
+    );
+}
+
+#[test]
+fn test_polynomial_ring() {
+    let ring = PolynomialRing::new(&["a1", "a2", "t"], MonomialOrder::Lex);
+    let a1: Polynomial = ring.var("a1");
+    let a2: Polynomial = ring.var("a2");
+    let t: Polynomial = ring.var("t");
+
+    let f = &a1 * &a1 - &a2 * &t + ring.constant(Rational::from(2));
+    assert_eq!(f.to_string(), "(a1)^2 - (a2)(t) + 2 Lex");
+    assert_eq!(ring.to_string(), "[a1, a2, t] Lex");
+
+    // 同じ変数の数と順序でも、変数名が違えば混ぜられない
+    let other = PolynomialRing::new(&["x", "y", "z"], MonomialOrder::Lex);
+    let x: Polynomial = other.var("x");
+    assert!(matches!(f.try_add(&x), Err(RingError::Rings(_, _))));
+    assert!(matches!(f.try_mul(&x), Err(RingError::Rings(_, _))));
+
+    let grlex = PolynomialRing::new(&["a1", "a2", "t"], MonomialOrder::Grlex);
+    let g: Polynomial = grlex.var("a1");
+    assert!(matches!(f.try_sub(&g), Err(RingError::MonomialOrder(_, _))));
+
+    // 異なる環の多項式は panic せず等しくないとする
+    assert_ne!(x, ring.var::<Rational>("a1"));
+    assert_ne!(g, a1);
+    let two = PolynomialRing::new(&["a1", "a2"], MonomialOrder::Lex);
+    assert_ne!(
+        two.constant(Rational::from(2)),
+        ring.constant(Rational::from(2))
+    );
+    assert_eq!(ring.var::<Rational>("a1"), a1);
+
+    // 環を持たない多項式は相手の環に合わせる
+    let h = Polynomial::from((
+        Rational::from(3),
+        Monomial::from(vec![0, 1, 0]),
+        MonomialOrder::Lex,
+    ));
+    let h = f.try_add(&h).unwrap();
+    assert_eq!(h.to_string(), "(a1)^2 - (a2)(t) + 3(a2) + 2 Lex");
+    assert!(std::sync::Arc::ptr_eq(h.ring().unwrap(), &ring));
+
+    // 割り算の商と余りも同じ環に属する
+    let (q, r) = f.polynomial_divide(&[&a1 - &a2]);
+    assert_eq!(q[0].to_string(), "(a1) + (a2) Lex");
+    assert_eq!(r.to_string(), "(a2)^2 - (a2)(t) + 2 Lex");
+}
+
+#[test]
+#[should_panic(expected = "different polynomial rings")]
+fn test_polynomial_ring_mismatch() {
+    let r1 = PolynomialRing::new(&["x", "y"], MonomialOrder::Lex);
+    let r2 = PolynomialRing::new(&["u", "v"], MonomialOrder::Lex);
+    let x: Polynomial = r1.var("x");
+    let u: Polynomial = r2.var("u");
+    let _ = &x + &u;
+}
+
+#[test]
+#[should_panic(expected = "different monomial orders")]
+fn test_s_polynomial_ring_mismatch() {
+    let r1 = PolynomialRing::new(&["x", "y"], MonomialOrder::Lex);
+    let r2 = PolynomialRing::new(&["x", "y"], MonomialOrder::Grlex);
+    let _ = s_polynomial(&r1.parse("x y").unwrap(), &r2.parse("x^2").unwrap());
+}
+
+#[test]
+fn test_parse() {
+    let ring = PolynomialRing::new(&["a1", "b1", "c1"], MonomialOrder::Grlex);
+    let a1: Polynomial = ring.var("a1");
+    let b1: Polynomial = ring.var("b1");
+    let c1: Polynomial = ring.var("c1");
+    let two = ring.constant(Rational::from(2));
+
+    let f = ring.parse("a1^2 - 2*a1*b1 + (c1 - b1)^2 - 3/4").unwrap();
+    let g = &a1 * &a1 - &two * &a1 * &b1 + (&c1 - &b1) * (&c1 - &b1)
+        - ring.constant(Rational::from(3) / Rational::from(4));
+    assert_eq!(f, g);
+    assert_eq!(
+        f.to_string(),
+        "(a1)^2 - 2(a1)(b1) + (b1)^2 - 2(b1)(c1) + (c1)^2 - 3/4 Grlex"
+    );
+
+    // 並べただけの積、単項のマイナス、冪は積より強い
+    assert_eq!(ring.parse("2 a1 b1").unwrap(), &two * &a1 * &b1);
+    assert_eq!(
+        ring.parse("-a1^2").unwrap(),
+        ring.parse("0 - a1 a1").unwrap()
+    );
+    assert_eq!(ring.parse("(a1 + 1)^0").unwrap(), ring.parse("1").unwrap());
+    assert_eq!(
+        ring.parse("a1 / (4 - 2)").unwrap(),
+        ring.parse("1/2 a1").unwrap()
+    );
+
+    let err = |s: &str| ring.parse(s).unwrap_err();
+    assert_eq!(
+        err("a1 + z"),
+        ParseError::UnknownVariable(5, "z".to_string())
+    );
+    assert_eq!(err("a1 ^ -1"), ParseError::InvalidExponent(5));
+    assert_eq!(err("a1 / b1"), ParseError::InvalidDivisor(5));
+    assert_eq!(err("a1 / 0"), ParseError::InvalidDivisor(5));
+    assert_eq!(err("(a1 + b1"), ParseError::UnexpectedEnd(8));
+    assert_eq!(
+        err("a1 + b1)"),
+        ParseError::UnexpectedToken(7, ")".to_string())
+    );
+    assert_eq!(err("a1 $ b1"), ParseError::UnexpectedChar(3, '$'));
+    assert_eq!(err(""), ParseError::UnexpectedEnd(0));
+    assert_eq!(err("a1 b1 ^ x").position(), 8);
+
+    // 指数が u32 に収まらない時や ±1 倍の単項式以外の大きすぎる冪は panic せずエラーにする
+    assert!(ring.parse("a1^4294967295").is_ok());
+    assert_eq!(err("a1^4294967295*a1"), ParseError::InvalidExponent(14));
+    assert_eq!(err("a1^4294967295 a1"), ParseError::InvalidExponent(14));
+    assert_eq!(err("(a1 b1^2)^2147483648"), ParseError::InvalidExponent(10));
+    assert_eq!(err("2^4000000000"), ParseError::InvalidExponent(2));
+    assert_eq!(err("(2 a1)^4000000000"), ParseError::InvalidExponent(7));
+    assert_eq!(err("(a1 + 1)^65536"), ParseError::InvalidExponent(9));
+    assert_eq!(
+        ring.parse("(-1)^4000000001").unwrap(),
+        ring.parse("-1").unwrap()
+    );
+}
+
+#[test]
+fn test_with_monomial_order() {
+    let ring = PolynomialRing::new(&["x", "y", "z"], MonomialOrder::Lex);
+    let f = ring.parse("x z + y^3 - z^2").unwrap();
+    let g = ring.parse("y + z^4").unwrap();
+    assert_eq!(f.fetch_lm(), Some(ring.monomial(vec![1, 0, 1])));
+
+    let f = f.with_monomial_order(MonomialOrder::Grevlex);
+    assert_eq!(f.get_monomial_order(), MonomialOrder::Grevlex);
+    assert_eq!(f.to_string(), "(y)^3 + (x)(z) - (z)^2 Grevlex");
+    assert_eq!(
+        f.ring().unwrap().get_monomial_order(),
+        MonomialOrder::Grevlex
+    );
+    assert_eq!(f.ring().unwrap().names(), ring.names());
+
+    let mut h = f.clone();
+    h.set_monomial_order(MonomialOrder::Lex);
+    assert_eq!(h.to_string(), "(x)(z) + (y)^3 - (z)^2 Lex");
+    assert_eq!(h, ring.parse("x z + y^3 - z^2").unwrap());
+
+    let fs = with_monomial_order(vec![h, g], &MonomialOrder::Grlex);
+    assert_eq!(
+        fs.iter()
+            .map(|f| f.fetch_lm().unwrap().exponents().to_vec())
+            .collect::<Vec<_>>(),
+        vec![vec![0, 3, 0], vec![0, 0, 4]]
+    );
+    assert!(std::sync::Arc::ptr_eq(
+        fs[0].ring().unwrap(),
+        fs[1].ring().unwrap()
+    ));
+    assert_eq!(
+        (&fs[0] + &fs[1]).to_string(),
+        "(z)^4 + (y)^3 + (x)(z) - (z)^2 + (y) Grlex"
+    );
+}
+
+#[test]
+fn test_terms() {
+    use super::terms::Terms;
+
+    let ring = PolynomialRing::new(&["x", "y"], MonomialOrder::Grlex);
+    let f = ring.parse("x^2 + 3 x y - y + 1").unwrap();
+    let g = ring.parse("y^3 - 3 x y + y").unwrap();
+
+    // 併合で足すと打ち消し合った項は残らず、昇順に並ぶ
+    let h = &f + &g;
+    assert_eq!(h.to_string(), "(y)^3 + (x)^2 + 1 Grlex");
+    assert!(h
+        .terms()
+        .zip(h.terms().skip(1))
+        .all(|((l, _), (r, _))| l < r));
+    assert_eq!(&h - &g, f);
+    assert_eq!((&f - &f).terms().count(), 0);
+    assert_eq!(h.fetch_lm(), Some(ring.monomial(vec![0, 3])));
+
+    let terms = f
+        .terms()
+        .chain(f.terms())
+        .map(|(m, c)| (m.clone(), c.clone()))
+        .rev()
+        .collect::<Terms<Rational>>();
+    assert_eq!(terms, (&f + &f).terms);
+    assert_eq!(
+        terms.prev(&ring.monomial(vec![1, 1])),
+        Some(&ring.monomial(vec![0, 1]))
+    );
+    assert_eq!(terms.prev(&ring.monomial(vec![0, 0])), None);
+}
+
+#[test]
+fn test_geobucket() {
+    use super::geobucket::Geobucket;
+
+    let ring = PolynomialRing::new(&["x", "y", "z"], MonomialOrder::Grevlex);
+    let f = ring.parse("(x + 2 y - z + 1)^6").unwrap();
+    let g = ring.parse("(x - y + 3 z)^3").unwrap();
+
+    // 大きさの違う列を足しても和は変わらない
+    let mut p = Geobucket::new();
+    p.add(f.terms.clone());
+    for _ in 0..5 {
+        p.add(g.terms.clone());
+    }
+    p.add(f.map_coeff(|c| -c.clone()).terms);
+    let mut terms = Vec::new();
+    while let Some(t) = p.pop_leading() {
+        terms.push(t);
+    }
+    terms.reverse();
+    assert_eq!(
+        terms,
+        (&g * &ring.constant(Rational::from(5)))
+            .terms
+            .into_iter()
+            .collect::<Vec<_>>()
+    );
+
+    // 割り算の結果は f = a0 f0 + a1 f1 + r を満たし、r のどの項も主項で割り切れない
+    let fs = [
+        ring.parse("x^2 y - z^3 + y").unwrap(),
+        ring.parse("x z^2 - 2 y + 1").unwrap(),
+    ];
+    let (a, r) = f.polynomial_divide(&fs);
+    assert_eq!(&(&(&a[0] * &fs[0]) + &(&a[1] * &fs[1])) + &r, f);
+    assert!(r.terms().all(|(m, _)| fs
+        .iter()
+        .all(|fi| !m.is_divisible_by(&fi.fetch_lm().unwrap()))));
+    assert_eq!(r.ring, f.ring);
+    // 余りだけを求めても同じ
+    assert_eq!(f.reduce(&[&fs[0], &fs[1]]), r);
+}
+
+#[test]
+fn test_mul() {
+    let ring = PolynomialRing::new(&["x", "y", "z"], MonomialOrder::Grevlex);
+    let f = ring.parse("x^2 - 2 x y + 3 z - 1").unwrap();
+    let g = ring.parse("x^3 + 2 y^2 - z + 5").unwrap();
+
+    // ヒープでの積は項ごとに足した結果と一致する
+    let mut h = ring.zero();
+    for (m, c) in f.terms() {
+        let mut d = g.clone();
+        d.mul_term(c, m);
+        h = &h + &d;
+    }
+    assert_eq!(&f * &g, h);
+    assert_eq!(&g * &f, h);
+    assert_eq!(&(&f - &g) * &(&f + &g), &(&f * &f) - &(&g * &g));
+    assert_eq!(&f * &ring.zero(), ring.zero());
+
+    let mut k = f.clone();
+    k *= &g;
+    assert_eq!(k, h);
+
+    let mut k = f.clone();
+    k.mul_monomial(&ring.monomial(vec![1, 0, 2]));
+    assert_eq!(k, &f * &ring.parse("x z^2").unwrap());
+    assert_eq!(
+        k.to_string(),
+        "(x)^3(z)^2 - 2(x)^2(y)(z)^2 + 3(x)(z)^3 - (x)(z)^2 Grevlex"
+    );
+}
+
+#[test]
+fn test_assign_ops() {
+    let ring = PolynomialRing::new(&["x", "y"], MonomialOrder::Lex);
+    let f = ring.parse("x^2 - 3 x y + 1/2").unwrap();
+    let g = ring.parse("x y + y^2 - 2").unwrap();
+
+    let mut h = f.clone();
+    h += &g;
+    assert_eq!(h, &f + &g);
+    h -= g.clone();
+    assert_eq!(h, f);
+    h *= &g;
+    assert_eq!(h, &f * &g);
+
+    // 途中の項が打ち消し合っても昇順のまま詰まる
+    let k = ring.parse("y^3 + 3 x y - x^3 + y - 7").unwrap();
+    let mut h = f.clone();
+    h += &k;
+    assert_eq!(h, &f + &k);
+    assert_eq!(h.to_string(), " - (x)^3 + (x)^2 + (y)^3 + (y) - 13/2 Lex");
+    h -= &h.clone();
+    assert_eq!(h, ring.zero());
+    let mut h = ring.zero();
+    h -= &f;
+    h += k.clone();
+    assert_eq!(h, &k - &f);
+
+    assert_eq!(-&f, ring.parse("-x^2 + 3 x y - 1/2").unwrap());
+    assert_eq!(&f + &-f.clone(), ring.zero());
+
+    // スカラー倍
+    assert_eq!(&f * 2, ring.parse("2 x^2 - 6 x y + 1").unwrap());
+    assert_eq!(&f * &Rational::from(0), ring.zero());
+    let mut h = f.clone();
+    h *= &Integer::from(-4);
+    assert_eq!(h, &f * &ring.constant(Rational::from(-4)));
+    h *= 1;
+    assert_eq!(h, &f * -4);
+    h *= &Rational::from(0);
+    assert_eq!(h, ring.zero());
+    let mut k = Polynomial::<Integer>::new(2, MonomialOrder::Lex);
+    k.add_term(Integer::from(2), ring.monomial(vec![1, 0]));
+    k.add_term(Integer::from(-3), ring.monomial(vec![0, 1]));
+    assert_eq!((&k * 3).to_string(), "6(x_1) - 9(x_2) Lex");
+
+    // self - c m g
+    let m = ring.monomial(vec![1, 0]);
+    let mut h = f.clone();
+    h.sub_mul_term(&Rational::from(3), &m, &g);
+    let mut d = g.clone();
+    d.mul_term(&Rational::from(3), &m);
+    assert_eq!(h, &f - &d);
+    let mut h = ring.parse("x y").unwrap();
+    h.sub_mul_term(&Rational::from(1), &ring.monomial(vec![0, 0]), &h.clone());
+    assert_eq!(h, ring.zero());
+}
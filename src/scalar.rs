@@ -1,9 +1,43 @@
+// 多倍長整数の実装を選ぶ (両方有効なら gmp を使う)
+#[cfg(not(any(feature = "gmp", feature = "pure-rust")))]
+compile_error!(
+    "either feature \"gmp\" (default) or \"pure-rust\" must be enabled; \
+     use `--no-default-features --features pure-rust` to build without libgmp"
+);
+
+#[cfg(feature = "gmp")]
 use gmp::mpq::Mpq;
+#[cfg(feature = "gmp")]
 use gmp::mpz::Mpz;
 
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+mod algebraic;
+mod bigint;
+mod bigrational;
+mod fp;
+mod fq;
+mod rational_function;
+mod univariate;
+pub use algebraic::{AlgebraicNumber, NumberField};
+pub use bigint::{BigInt, ParseBigIntError};
+pub use bigrational::BigRational;
+pub use fp::Fp;
+pub use fq::{FiniteField, Fq};
+pub use rational_function::RationalFunction;
+
+#[cfg(feature = "gmp")]
 pub type Integer = Mpz;
+#[cfg(feature = "gmp")]
 pub type Rational = Mpq;
 
+// GMP (libgmp) を使わない実装
+#[cfg(all(feature = "pure-rust", not(feature = "gmp")))]
+pub type Integer = BigInt;
+#[cfg(all(feature = "pure-rust", not(feature = "gmp")))]
+pub type Rational = BigRational;
+
 pub fn gcd(a: &Integer, b: &Integer) -> Integer {
     if b == &Integer::zero() {
         a.clone()
@@ -16,3 +50,135 @@ pub fn gcd(a: &Integer, b: &Integer) -> Integer {
 pub fn lcm(a: &Integer, b: &Integer) -> Integer {
     a * b / gcd(a, b)
 }
+
+/// [0, m) に入る a mod m
+pub fn modulo(a: &Integer, m: &Integer) -> Integer {
+    let r = a % m;
+    if r < Integer::zero() {
+        r + m
+    } else {
+        r
+    }
+}
+
+/// 床関数による商 floor(a / b)
+pub fn div_floor(a: &Integer, b: &Integer) -> Integer {
+    let q = a / b;
+    let r = a - &(&q * b);
+    if !r.is_zero() && ((r < Integer::zero()) != (*b < Integer::zero())) {
+        q - Integer::one()
+    } else {
+        q
+    }
+}
+
+/// 拡張ユークリッド互除法: a * s + b * t = g となる (g, s, t)
+pub fn gcdext(a: &Integer, b: &Integer) -> (Integer, Integer, Integer) {
+    let (mut r0, mut r1) = (a.clone(), b.clone());
+    let (mut s0, mut s1) = (Integer::one(), Integer::zero());
+    let (mut t0, mut t1) = (Integer::zero(), Integer::one());
+
+    while r1 != Integer::zero() {
+        let q = &r0 / &r1;
+        let r2 = &r0 - &(&q * &r1);
+        let s2 = &s0 - &(&q * &s1);
+        let t2 = &t0 - &(&q * &t1);
+        r0 = r1;
+        r1 = r2;
+        s0 = s1;
+        s1 = s2;
+        t0 = t1;
+        t1 = t2;
+    }
+
+    (r0, s0, t0)
+}
+
+/// 中国剰余定理: x = a (mod m), x = b (mod n) となる [0, mn) の x (m, n は互いに素)
+pub fn crt(a: &Integer, m: &Integer, b: &Integer, n: &Integer) -> Integer {
+    let (g, m_inv, _) = gcdext(&modulo(m, n), n);
+    assert_eq!(g, Integer::one());
+
+    let k = modulo(&(&(b - a) * &m_inv), n);
+
+    modulo(&(a + &(m * &k)), &(m * n))
+}
+
+/// 有理数復元: a = r / s (mod m) かつ 2r^2 < m, 2s^2 < m となる r / s
+pub fn rational_reconstruction(a: &Integer, m: &Integer) -> Option<Rational> {
+    let two = Integer::from(2);
+
+    let (mut r0, mut r1) = (m.clone(), modulo(a, m));
+    let (mut s0, mut s1) = (Integer::zero(), Integer::one());
+
+    while &(&two * &r1) * &r1 >= *m {
+        let q = &r0 / &r1;
+        let r2 = &r0 - &(&q * &r1);
+        let s2 = &s0 - &(&q * &s1);
+        r0 = r1;
+        r1 = r2;
+        s0 = s1;
+        s1 = s2;
+    }
+
+    if &(&two * &s1) * &s1 >= *m || gcd(&r1, &s1).abs() != Integer::one() {
+        return None;
+    }
+
+    Some(Rational::from(r1) / Rational::from(s1))
+}
+
+/// 多項式の係数環
+pub trait Ring:
+    Clone
+    + PartialEq
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    fn is_zero(&self) -> bool;
+}
+
+/// 多項式の係数体
+pub trait Field: Ring + Div<Output = Self> {
+    /// 乗法逆元 (0の逆元は未定義)
+    fn inv(&self) -> Self;
+}
+
+impl Ring for Integer {
+    fn zero() -> Self {
+        Integer::zero()
+    }
+    fn one() -> Self {
+        Integer::one()
+    }
+    fn is_zero(&self) -> bool {
+        Integer::is_zero(self)
+    }
+}
+
+impl Ring for Rational {
+    fn zero() -> Self {
+        Rational::zero()
+    }
+    fn one() -> Self {
+        Rational::one()
+    }
+    fn is_zero(&self) -> bool {
+        Rational::is_zero(self)
+    }
+}
+
+impl Field for Rational {
+    fn inv(&self) -> Self {
+        self.invert()
+    }
+}
+
+mod test;
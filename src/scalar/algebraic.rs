@@ -0,0 +1,244 @@
+use crate::scalar::{univariate, Field, Rational, Ring};
+
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::sync::Arc;
+
+/// 代数体 Q(a) = Q[t]/(m(t)) (m は Q 上既約)
+#[derive(Clone, PartialEq, Debug)]
+pub struct NumberField {
+    // 最小多項式 (モニック, 係数は次数の昇順)
+    minimal_polynomial: Vec<Rational>,
+    name: String,
+}
+
+impl NumberField {
+    /// 最小多項式 m の係数を次数の昇順で与える (既約性は呼び出し側が保証する)
+    pub fn new(minimal_polynomial: Vec<Rational>, name: &str) -> Arc<Self> {
+        let m = univariate::trim(minimal_polynomial);
+        assert!(m.len() >= 2, "minimal polynomial must have positive degree");
+
+        let lc_inv = m[m.len() - 1].invert();
+        Arc::new(Self {
+            minimal_polynomial: m.into_iter().map(|c| c * lc_inv.clone()).collect(),
+            name: name.to_string(),
+        })
+    }
+
+    pub fn degree(&self) -> usize {
+        self.minimal_polynomial.len() - 1
+    }
+
+    pub fn minimal_polynomial(&self) -> &[Rational] {
+        &self.minimal_polynomial
+    }
+
+    /// 生成元 a
+    pub fn generator(self: &Arc<Self>) -> AlgebraicNumber {
+        self.element(vec![Rational::zero(), Rational::one()])
+    }
+
+    /// c_0 + c_1 a + c_2 a^2 + ...
+    pub fn element(self: &Arc<Self>, coeffs: Vec<Rational>) -> AlgebraicNumber {
+        AlgebraicNumber::reduce(coeffs, Some(self.clone()))
+    }
+}
+
+/// 代数体 Q(a) の元
+///
+/// 0, 1 などの有理数は体を持たない定数として表し、演算時に相手の体に属するものとして扱う。
+#[derive(Clone)]
+pub struct AlgebraicNumber {
+    // 係数は次数の昇順 (次数は最小多項式の次数未満)
+    coeffs: Vec<Rational>,
+    field: Option<Arc<NumberField>>,
+}
+
+impl AlgebraicNumber {
+    fn reduce(coeffs: Vec<Rational>, field: Option<Arc<NumberField>>) -> Self {
+        let coeffs = match &field {
+            Some(field) if coeffs.len() > field.degree() => {
+                univariate::div_rem(&coeffs, &field.minimal_polynomial).1
+            }
+            _ => univariate::trim(coeffs),
+        };
+        Self { coeffs, field }
+    }
+
+    // 体を持たない定数はどの体の元とも比べられる
+    fn same_field(&self, other: &Self) -> bool {
+        match (&self.field, &other.field) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b) || a == b,
+            _ => true,
+        }
+    }
+
+    fn common_field(&self, other: &Self) -> Option<Arc<NumberField>> {
+        match (&self.field, &other.field) {
+            (Some(a), Some(_)) => {
+                assert!(
+                    self.same_field(other),
+                    "elements of different number fields"
+                );
+                Some(a.clone())
+            }
+            (Some(a), None) => Some(a.clone()),
+            (None, b) => b.clone(),
+        }
+    }
+
+    /// 生成元に関する係数 (次数の昇順)
+    pub fn coeffs(&self) -> &[Rational] {
+        &self.coeffs
+    }
+
+    pub fn field(&self) -> Option<&Arc<NumberField>> {
+        self.field.as_ref()
+    }
+}
+
+impl From<Rational> for AlgebraicNumber {
+    fn from(c: Rational) -> Self {
+        Self::reduce(vec![c], None)
+    }
+}
+
+impl From<i64> for AlgebraicNumber {
+    fn from(c: i64) -> Self {
+        Self::from(Rational::from(c))
+    }
+}
+
+impl PartialEq for AlgebraicNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.coeffs == other.coeffs && self.same_field(other)
+    }
+}
+
+impl Display for AlgebraicNumber {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.coeffs.is_empty() {
+            return write!(f, "0");
+        }
+        let name = self.field.as_ref().map_or("a", |field| &field.name);
+
+        let mut output = String::new();
+        for (i, c) in self.coeffs.iter().enumerate().rev() {
+            if c.is_zero() {
+                continue;
+            }
+            let c = c.to_string();
+            let (is_negative, abs_c) = match c.strip_prefix('-') {
+                Some(abs_c) => (true, abs_c),
+                None => (false, c.as_str()),
+            };
+
+            if is_negative {
+                output.push_str(if output.is_empty() { "-" } else { " - " });
+            } else if !output.is_empty() {
+                output.push_str(" + ");
+            }
+
+            if i == 0 || abs_c != "1" {
+                output.push_str(abs_c);
+                if i > 0 {
+                    output.push('*');
+                }
+            }
+            match i {
+                0 => {}
+                1 => output.push_str(name),
+                _ => output.push_str(&format!("{}^{}", name, i)),
+            }
+        }
+        write!(f, "{}", output)
+    }
+}
+
+impl Debug for AlgebraicNumber {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        Display::fmt(self, f)
+    }
+}
+
+impl Add for AlgebraicNumber {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let field = self.common_field(&other);
+        Self::reduce(univariate::add(&self.coeffs, &other.coeffs), field)
+    }
+}
+
+impl Sub for AlgebraicNumber {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let field = self.common_field(&other);
+        Self::reduce(univariate::sub(&self.coeffs, &other.coeffs), field)
+    }
+}
+
+impl Mul for AlgebraicNumber {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let field = self.common_field(&other);
+        Self::reduce(univariate::mul(&self.coeffs, &other.coeffs), field)
+    }
+}
+
+impl Div for AlgebraicNumber {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inv()
+    }
+}
+
+impl Neg for AlgebraicNumber {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            coeffs: univariate::neg(&self.coeffs),
+            field: self.field,
+        }
+    }
+}
+
+impl Ring for AlgebraicNumber {
+    fn zero() -> Self {
+        Self {
+            coeffs: Vec::new(),
+            field: None,
+        }
+    }
+    fn one() -> Self {
+        Self::from(Rational::one())
+    }
+    fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+}
+
+impl Field for AlgebraicNumber {
+    fn inv(&self) -> Self {
+        assert!(!self.is_zero(), "division by zero in number field");
+
+        // 有理数
+        if self.coeffs.len() == 1 {
+            return Self {
+                coeffs: vec![self.coeffs[0].invert()],
+                field: self.field.clone(),
+            };
+        }
+
+        let field = self.field.as_ref().unwrap();
+        // 拡張ユークリッド互除法
+        let coeffs = univariate::inverse_mod(&self.coeffs, &field.minimal_polynomial)
+            .expect("minimal polynomial is not irreducible");
+        Self::reduce(coeffs, self.field.clone())
+    }
+}
@@ -0,0 +1,534 @@
+// GMP を使わない多倍長整数 (feature "pure-rust" で scalar::Integer になる)
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+/// 多倍長整数 (符号と絶対値, 絶対値は 2^64 進の下位桁から)
+///
+/// 演算は Mpz と同じく、除算は0方向への切り捨てで剰余の符号は被除数に従う。
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    mag: Vec<u64>,
+}
+
+fn trim(mut a: Vec<u64>) -> Vec<u64> {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+    a
+}
+
+fn cmp_mag(a: &[u64], b: &[u64]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut ret = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u128;
+    for (i, ai) in a.iter().enumerate() {
+        let s = *ai as u128 + *b.get(i).unwrap_or(&0) as u128 + carry;
+        ret.push(s as u64);
+        carry = s >> 64;
+    }
+    if carry > 0 {
+        ret.push(carry as u64);
+    }
+    ret
+}
+
+// a >= b
+fn sub_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut ret = Vec::with_capacity(a.len());
+    let mut borrow = 0u64;
+    for (i, ai) in a.iter().enumerate() {
+        let (d, o1) = ai.overflowing_sub(*b.get(i).unwrap_or(&0));
+        let (d, o2) = d.overflowing_sub(borrow);
+        ret.push(d);
+        borrow = (o1 || o2) as u64;
+    }
+    debug_assert_eq!(borrow, 0);
+    trim(ret)
+}
+
+fn mul_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut ret = vec![0u64; a.len() + b.len()];
+    for (i, ai) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, bj) in b.iter().enumerate() {
+            let t = *ai as u128 * *bj as u128 + ret[i + j] as u128 + carry;
+            ret[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        ret[i + b.len()] = carry as u64;
+    }
+    trim(ret)
+}
+
+// 1桁で割る (商, 余り)
+fn divrem_mag_small(a: &[u64], b: u64) -> (Vec<u64>, u64) {
+    let mut q = vec![0u64; a.len()];
+    let mut r = 0u128;
+    for i in (0..a.len()).rev() {
+        let t = (r << 64) | a[i] as u128;
+        q[i] = (t / b as u128) as u64;
+        r = t % b as u128;
+    }
+    (trim(q), r as u64)
+}
+
+fn shl_bits(a: &[u64], s: u32) -> Vec<u64> {
+    if s == 0 {
+        return a.to_vec();
+    }
+    let mut ret = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u64;
+    for ai in a {
+        ret.push((ai << s) | carry);
+        carry = ai >> (64 - s);
+    }
+    ret.push(carry);
+    ret
+}
+
+fn shr_bits(a: &[u64], s: u32) -> Vec<u64> {
+    if s == 0 {
+        return trim(a.to_vec());
+    }
+    let mut ret = vec![0u64; a.len()];
+    for i in 0..a.len() {
+        let hi = if i + 1 < a.len() {
+            a[i + 1] << (64 - s)
+        } else {
+            0
+        };
+        ret[i] = (a[i] >> s) | hi;
+    }
+    trim(ret)
+}
+
+// Knuth のアルゴリズム D (商, 余り)
+fn divrem_mag(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    assert!(!b.is_empty(), "divide by zero");
+    if cmp_mag(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (q, r) = divrem_mag_small(a, b[0]);
+        return (q, trim(vec![r]));
+    }
+
+    const BASE: u128 = 1 << 64;
+
+    // 除数の最上位桁の最上位ビットが立つように正規化する
+    let s = b[b.len() - 1].leading_zeros();
+    let v = trim(shl_bits(b, s));
+    let mut u = shl_bits(a, s);
+    if u.len() == a.len() {
+        u.push(0);
+    }
+
+    let n = v.len();
+    let m = u.len() - n - 1;
+    let mut q = vec![0u64; m + 1];
+
+    for j in (0..=m).rev() {
+        let num = ((u[j + n] as u128) << 64) | u[j + n - 1] as u128;
+        let mut qhat = num / v[n - 1] as u128;
+        let mut rhat = num % v[n - 1] as u128;
+        while qhat >= BASE || qhat * v[n - 2] as u128 > ((rhat << 64) | u[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += v[n - 1] as u128;
+            if rhat >= BASE {
+                break;
+            }
+        }
+
+        // u[j..=j+n] -= qhat * v
+        let mut borrow = 0i128;
+        let mut carry = 0u128;
+        for i in 0..n {
+            let p = qhat * v[i] as u128 + carry;
+            carry = p >> 64;
+            let t = u[i + j] as i128 - borrow - (p as u64) as i128;
+            u[i + j] = t as u64;
+            borrow = (t < 0) as i128;
+        }
+        let t = u[j + n] as i128 - borrow - carry as i128;
+        u[j + n] = t as u64;
+
+        // 引きすぎたら足し戻す
+        if t < 0 {
+            qhat -= 1;
+            let mut carry = 0u128;
+            for i in 0..n {
+                let s = u[i + j] as u128 + v[i] as u128 + carry;
+                u[i + j] = s as u64;
+                carry = s >> 64;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u64);
+        }
+        q[j] = qhat as u64;
+    }
+
+    (trim(q), shr_bits(&u[..n], s))
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, mag: Vec<u64>) -> Self {
+        let mag = trim(mag);
+        Self {
+            negative: negative && !mag.is_empty(),
+            mag,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn one() -> Self {
+        Self::from(1u64)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_empty()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::from_parts(false, self.mag.clone())
+    }
+
+    /// 0方向への切り捨てによる (商, 余り)
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        let (q, r) = divrem_mag(&self.mag, &other.mag);
+        (
+            Self::from_parts(self.negative != other.negative, q),
+            Self::from_parts(self.negative, r),
+        )
+    }
+
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    pub fn lcm(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        (self / &self.gcd(other) * other).abs()
+    }
+
+    pub fn pow(&self, exp: u32) -> Self {
+        let mut ret = Self::one();
+        let mut base = self.clone();
+        let mut e = exp;
+        while e > 0 {
+            if e & 1 == 1 {
+                ret = &ret * &base;
+            }
+            base = &base * &base;
+            e >>= 1;
+        }
+        ret
+    }
+
+    pub fn to_str_radix(&self, base: u8) -> String {
+        assert!((2..=36).contains(&base));
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let mut digits = Vec::new();
+        let mut mag = self.mag.clone();
+        while !mag.is_empty() {
+            let (q, r) = divrem_mag_small(&mag, base as u64);
+            digits.push(std::char::from_digit(r as u32, base as u32).unwrap());
+            mag = q;
+        }
+        if self.negative {
+            digits.push('-');
+        }
+        digits.iter().rev().collect()
+    }
+
+    pub fn from_str_radix(s: &str, base: u8) -> Result<Self, ParseBigIntError> {
+        assert!((2..=36).contains(&base));
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(ParseBigIntError);
+        }
+
+        let mut mag = Vec::new();
+        for c in digits.chars() {
+            let d = c.to_digit(base as u32).ok_or(ParseBigIntError)?;
+            mag = add_mag(&mul_mag(&mag, &[base as u64]), &trim(vec![d as u64]));
+        }
+        Ok(Self::from_parts(negative, mag))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "invalid integer literal")
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(v: u64) -> Self {
+        Self::from_parts(false, vec![v])
+    }
+}
+
+impl From<u32> for BigInt {
+    fn from(v: u32) -> Self {
+        Self::from(v as u64)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(v: i64) -> Self {
+        Self::from_parts(v < 0, vec![v.unsigned_abs()])
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(v: i32) -> Self {
+        Self::from(v as i64)
+    }
+}
+
+impl From<&BigInt> for Option<u64> {
+    fn from(v: &BigInt) -> Self {
+        match (v.negative, v.mag.len()) {
+            (_, 0) => Some(0),
+            (false, 1) => Some(v.mag[0]),
+            _ => None,
+        }
+    }
+}
+
+impl From<&BigInt> for Option<i64> {
+    fn from(v: &BigInt) -> Self {
+        match (v.negative, v.mag.len()) {
+            (_, 0) => Some(0),
+            (false, 1) => i64::try_from(v.mag[0]).ok(),
+            (true, 1) => 0i64.checked_sub_unsigned(v.mag[0]),
+            _ => None,
+        }
+    }
+}
+
+impl From<&BigInt> for f64 {
+    fn from(v: &BigInt) -> Self {
+        let m = v
+            .mag
+            .iter()
+            .rev()
+            .fold(0f64, |m, d| m * 18_446_744_073_709_551_616f64 + *d as f64);
+        if v.negative {
+            -m
+        } else {
+            m
+        }
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}", self.to_str_radix(10))
+    }
+}
+
+impl Debug for BigInt {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        Display::fmt(self, f)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &other.mag),
+            (true, true) => cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        Self::from_parts(!self.negative, self.mag)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -self.clone()
+    }
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_mag(&self.mag, &other.mag));
+        }
+        match cmp_mag(&self.mag, &other.mag) {
+            Ordering::Less => BigInt::from_parts(other.negative, sub_mag(&other.mag, &self.mag)),
+            _ => BigInt::from_parts(self.negative, sub_mag(&self.mag, &other.mag)),
+        }
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &(-other)
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != other.negative,
+            mul_mag(&self.mag, &other.mag),
+        )
+    }
+}
+
+impl Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+// 参照どうしの演算から値と参照の組み合わせ, 代入演算子, プリミティブ整数との演算を作る
+macro_rules! impl_oper {
+    ($tr: ident, $meth: ident, $tr_assign: ident, $meth_assign: ident) => {
+        impl $tr<BigInt> for BigInt {
+            type Output = BigInt;
+
+            fn $meth(self, other: BigInt) -> BigInt {
+                (&self).$meth(&other)
+            }
+        }
+
+        impl $tr<&BigInt> for BigInt {
+            type Output = BigInt;
+
+            fn $meth(self, other: &BigInt) -> BigInt {
+                (&self).$meth(other)
+            }
+        }
+
+        impl $tr<BigInt> for &BigInt {
+            type Output = BigInt;
+
+            fn $meth(self, other: BigInt) -> BigInt {
+                self.$meth(&other)
+            }
+        }
+
+        impl $tr_assign<BigInt> for BigInt {
+            fn $meth_assign(&mut self, other: BigInt) {
+                *self = (&*self).$meth(&other);
+            }
+        }
+
+        impl $tr_assign<&BigInt> for BigInt {
+            fn $meth_assign(&mut self, other: &BigInt) {
+                *self = (&*self).$meth(other);
+            }
+        }
+
+        impl_oper!(primitive u64, $tr, $meth, $tr_assign, $meth_assign);
+        impl_oper!(primitive i64, $tr, $meth, $tr_assign, $meth_assign);
+    };
+    (primitive $t: ty, $tr: ident, $meth: ident, $tr_assign: ident, $meth_assign: ident) => {
+        impl $tr<$t> for BigInt {
+            type Output = BigInt;
+
+            fn $meth(self, other: $t) -> BigInt {
+                (&self).$meth(&BigInt::from(other))
+            }
+        }
+
+        impl $tr<$t> for &BigInt {
+            type Output = BigInt;
+
+            fn $meth(self, other: $t) -> BigInt {
+                self.$meth(&BigInt::from(other))
+            }
+        }
+
+        impl $tr_assign<$t> for BigInt {
+            fn $meth_assign(&mut self, other: $t) {
+                *self = (&*self).$meth(&BigInt::from(other));
+            }
+        }
+    };
+}
+
+impl_oper!(Add, add, AddAssign, add_assign);
+impl_oper!(Sub, sub, SubAssign, sub_assign);
+impl_oper!(Mul, mul, MulAssign, mul_assign);
+impl_oper!(Div, div, DivAssign, div_assign);
+impl_oper!(Rem, rem, RemAssign, rem_assign);
@@ -0,0 +1,290 @@
+// GMP を使わない多倍長有理数 (feature "pure-rust" で scalar::Rational になる)
+use crate::scalar::{BigInt, ParseBigIntError};
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// 多倍長有理数 (既約分数で分母は正)
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BigRational {
+    num: BigInt,
+    den: BigInt,
+}
+
+impl BigRational {
+    pub fn new() -> Self {
+        Self::zero()
+    }
+
+    /// num / den
+    pub fn ratio(num: &BigInt, den: &BigInt) -> Self {
+        assert!(!den.is_zero(), "divide by zero");
+
+        let g = num.gcd(den);
+        let (num, den) = if g.is_zero() {
+            (num.clone(), den.clone())
+        } else {
+            (num / &g, den / &g)
+        };
+        if den < BigInt::zero() {
+            Self {
+                num: -num,
+                den: -den,
+            }
+        } else {
+            Self { num, den }
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            num: BigInt::zero(),
+            den: BigInt::one(),
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            num: BigInt::one(),
+            den: BigInt::one(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    pub fn get_num(&self) -> BigInt {
+        self.num.clone()
+    }
+
+    pub fn get_den(&self) -> BigInt {
+        self.den.clone()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            num: self.num.abs(),
+            den: self.den.clone(),
+        }
+    }
+
+    pub fn invert(&self) -> Self {
+        Self::ratio(&self.den, &self.num)
+    }
+
+    pub fn floor(&self) -> BigInt {
+        let (q, r) = self.num.div_rem(&self.den);
+        if r < BigInt::zero() {
+            q - BigInt::one()
+        } else {
+            q
+        }
+    }
+
+    pub fn ceil(&self) -> BigInt {
+        -(-self).floor()
+    }
+}
+
+impl Default for BigRational {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl FromStr for BigRational {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((num, den)) => {
+                let den = den.parse::<BigInt>()?;
+                // 分母が0の時は ratio が panic するので先に弾く
+                if den.is_zero() {
+                    return Err(ParseBigIntError);
+                }
+                Ok(Self::ratio(&num.parse()?, &den))
+            }
+            None => Ok(Self::from(s.parse::<BigInt>()?)),
+        }
+    }
+}
+
+impl From<BigInt> for BigRational {
+    fn from(v: BigInt) -> Self {
+        Self {
+            num: v,
+            den: BigInt::one(),
+        }
+    }
+}
+
+impl From<&BigInt> for BigRational {
+    fn from(v: &BigInt) -> Self {
+        Self::from(v.clone())
+    }
+}
+
+impl From<i64> for BigRational {
+    fn from(v: i64) -> Self {
+        Self::from(BigInt::from(v))
+    }
+}
+
+impl From<i32> for BigRational {
+    fn from(v: i32) -> Self {
+        Self::from(BigInt::from(v))
+    }
+}
+
+impl From<u64> for BigRational {
+    fn from(v: u64) -> Self {
+        Self::from(BigInt::from(v))
+    }
+}
+
+impl From<u32> for BigRational {
+    fn from(v: u32) -> Self {
+        Self::from(BigInt::from(v))
+    }
+}
+
+impl From<&BigRational> for f64 {
+    fn from(v: &BigRational) -> Self {
+        f64::from(&v.num) / f64::from(&v.den)
+    }
+}
+
+impl Display for BigRational {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.den == BigInt::one() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Debug for BigRational {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        Display::fmt(self, f)
+    }
+}
+
+impl Ord for BigRational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.num * &other.den).cmp(&(&other.num * &self.den))
+    }
+}
+
+impl PartialOrd for BigRational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigRational {
+    type Output = BigRational;
+
+    fn neg(self) -> BigRational {
+        BigRational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Neg for &BigRational {
+    type Output = BigRational;
+
+    fn neg(self) -> BigRational {
+        -self.clone()
+    }
+}
+
+impl Add<&BigRational> for &BigRational {
+    type Output = BigRational;
+
+    fn add(self, other: &BigRational) -> BigRational {
+        BigRational::ratio(
+            &(&self.num * &other.den + &other.num * &self.den),
+            &(&self.den * &other.den),
+        )
+    }
+}
+
+impl Sub<&BigRational> for &BigRational {
+    type Output = BigRational;
+
+    fn sub(self, other: &BigRational) -> BigRational {
+        BigRational::ratio(
+            &(&self.num * &other.den - &other.num * &self.den),
+            &(&self.den * &other.den),
+        )
+    }
+}
+
+impl Mul<&BigRational> for &BigRational {
+    type Output = BigRational;
+
+    fn mul(self, other: &BigRational) -> BigRational {
+        BigRational::ratio(&(&self.num * &other.num), &(&self.den * &other.den))
+    }
+}
+
+impl Div<&BigRational> for &BigRational {
+    type Output = BigRational;
+
+    fn div(self, other: &BigRational) -> BigRational {
+        BigRational::ratio(&(&self.num * &other.den), &(&self.den * &other.num))
+    }
+}
+
+// 参照どうしの演算から値と参照の組み合わせと代入演算子を作る
+macro_rules! impl_oper {
+    ($tr: ident, $meth: ident, $tr_assign: ident, $meth_assign: ident) => {
+        impl $tr<BigRational> for BigRational {
+            type Output = BigRational;
+
+            fn $meth(self, other: BigRational) -> BigRational {
+                (&self).$meth(&other)
+            }
+        }
+
+        impl $tr<&BigRational> for BigRational {
+            type Output = BigRational;
+
+            fn $meth(self, other: &BigRational) -> BigRational {
+                (&self).$meth(other)
+            }
+        }
+
+        impl $tr<BigRational> for &BigRational {
+            type Output = BigRational;
+
+            fn $meth(self, other: BigRational) -> BigRational {
+                self.$meth(&other)
+            }
+        }
+
+        impl $tr_assign<BigRational> for BigRational {
+            fn $meth_assign(&mut self, other: BigRational) {
+                *self = (&*self).$meth(&other);
+            }
+        }
+
+        impl $tr_assign<&BigRational> for BigRational {
+            fn $meth_assign(&mut self, other: &BigRational) {
+                *self = (&*self).$meth(other);
+            }
+        }
+    };
+}
+
+impl_oper!(Add, add, AddAssign, add_assign);
+impl_oper!(Sub, sub, SubAssign, sub_assign);
+impl_oper!(Mul, mul, MulAssign, mul_assign);
+impl_oper!(Div, div, DivAssign, div_assign);
@@ -0,0 +1,284 @@
+use crate::scalar::{self, Field, Integer, Rational, Ring};
+
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// 素体 GF(P) の元 (P < 2^63 の素数)
+///
+/// P が奇数の時は Montgomery 表現 (R = 2^64) で保持し、乗算を u128 の積と
+/// Montgomery reduction だけで行う。
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp<const P: u64> {
+    v: u64,
+}
+
+// -P^{-1} mod 2^64 (Newton法)
+const fn neg_inv_mod_2_64(p: u64) -> u64 {
+    let mut inv = p;
+    let mut i = 0;
+    while i < 6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+// 2^128 mod P
+const fn r2_mod(p: u64) -> u64 {
+    if p < 2 {
+        return 0;
+    }
+    let r = (1u128 << 64) % (p as u128);
+    ((r * r) % (p as u128)) as u64
+}
+
+// a b mod m
+const fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128) * (b as u128) % (m as u128)) as u64
+}
+
+const fn pow_mod(a: u64, e: u64, m: u64) -> u64 {
+    let mut ret = 1 % m;
+    let mut base = a % m;
+    let mut e = e;
+    while e > 0 {
+        if e & 1 == 1 {
+            ret = mul_mod(ret, base, m);
+        }
+        base = mul_mod(base, base, m);
+        e >>= 1;
+    }
+    ret
+}
+
+// 素数判定 (2^64 未満ではこの12個の底での Miller-Rabin 判定で決まる)
+const fn is_prime(p: u64) -> bool {
+    if p < 2 {
+        return false;
+    }
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    let mut i = 0;
+    while i < BASES.len() {
+        if p.is_multiple_of(BASES[i]) {
+            return p == BASES[i];
+        }
+        i += 1;
+    }
+
+    let mut d = p - 1;
+    let mut s = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+    let mut i = 0;
+    while i < BASES.len() {
+        let mut x = pow_mod(BASES[i], d, p);
+        if x != 1 && x != p - 1 {
+            let mut r = 1;
+            while r < s && x != p - 1 {
+                x = mul_mod(x, x, p);
+                r += 1;
+            }
+            if x != p - 1 {
+                return false;
+            }
+        }
+        i += 1;
+    }
+    true
+}
+
+impl<const P: u64> Fp<P> {
+    // P が 2^63 未満の素数であることをコンパイル時に確かめる (元を作る関数から参照する)
+    const VALID_MODULUS: () = assert!(
+        P < 1 << 63 && is_prime(P),
+        "modulus of Fp must be a prime less than 2^63"
+    );
+    const MONTGOMERY: bool = P % 2 == 1;
+    const N_PRIME: u64 = neg_inv_mod_2_64(P);
+    const R2: u64 = r2_mod(P);
+
+    pub const fn modulus() -> u64 {
+        P
+    }
+
+    fn redc(t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(Self::N_PRIME);
+        let u = ((t + (m as u128) * (P as u128)) >> 64) as u64;
+        if u >= P {
+            u - P
+        } else {
+            u
+        }
+    }
+
+    fn from_canonical(v: u64) -> Self {
+        let () = Self::VALID_MODULUS;
+        debug_assert!(v < P);
+        if Self::MONTGOMERY {
+            Self {
+                v: Self::redc((v as u128) * (Self::R2 as u128)),
+            }
+        } else {
+            Self { v }
+        }
+    }
+
+    /// [0, P) の代表元
+    pub fn value(&self) -> u64 {
+        if Self::MONTGOMERY {
+            Self::redc(self.v as u128)
+        } else {
+            self.v
+        }
+    }
+
+    pub fn pow(&self, e: u64) -> Self {
+        let mut ret = Self::one();
+        let mut base = *self;
+        let mut e = e;
+        while e > 0 {
+            if e & 1 == 1 {
+                ret = ret * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        ret
+    }
+}
+
+impl<const P: u64> From<u64> for Fp<P> {
+    fn from(v: u64) -> Self {
+        Self::from_canonical(v % P)
+    }
+}
+
+impl<const P: u64> From<i64> for Fp<P> {
+    fn from(v: i64) -> Self {
+        let r = Self::from(v.unsigned_abs());
+        if v < 0 {
+            -r
+        } else {
+            r
+        }
+    }
+}
+
+impl<const P: u64> From<&Integer> for Fp<P> {
+    fn from(v: &Integer) -> Self {
+        let r: Option<u64> = (&scalar::modulo(v, &Integer::from(P))).into();
+        match r {
+            Some(r) => Self::from_canonical(r),
+            None => unreachable!(),
+        }
+    }
+}
+
+/// 分母が P で割り切れる時は像が存在しないので失敗する
+impl<const P: u64> TryFrom<&Rational> for Fp<P> {
+    type Error = ();
+
+    fn try_from(v: &Rational) -> Result<Self, Self::Error> {
+        let den = Self::from(&v.get_den());
+        if den.is_zero() {
+            return Err(());
+        }
+        Ok(Self::from(&v.get_num()) / den)
+    }
+}
+
+impl<const P: u64> Display for Fp<P> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}", self.value())
+    }
+}
+
+impl<const P: u64> Debug for Fp<P> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{} (mod {})", self.value(), P)
+    }
+}
+
+impl<const P: u64> Add for Fp<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // P < 2^63 なので溢れない
+        let s = self.v + other.v;
+        Self {
+            v: if s >= P { s - P } else { s },
+        }
+    }
+}
+
+impl<const P: u64> Sub for Fp<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            v: if self.v >= other.v {
+                self.v - other.v
+            } else {
+                self.v + P - other.v
+            },
+        }
+    }
+}
+
+impl<const P: u64> Mul for Fp<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let t = (self.v as u128) * (other.v as u128);
+        Self {
+            v: if Self::MONTGOMERY {
+                Self::redc(t)
+            } else {
+                (t % (P as u128)) as u64
+            },
+        }
+    }
+}
+
+impl<const P: u64> Div for Fp<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inv()
+    }
+}
+
+impl<const P: u64> Neg for Fp<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            v: if self.v == 0 { 0 } else { P - self.v },
+        }
+    }
+}
+
+impl<const P: u64> Ring for Fp<P> {
+    fn zero() -> Self {
+        let () = Self::VALID_MODULUS;
+        Self { v: 0 }
+    }
+    fn one() -> Self {
+        Self::from_canonical(1)
+    }
+    fn is_zero(&self) -> bool {
+        self.v == 0
+    }
+}
+
+impl<const P: u64> Field for Fp<P> {
+    fn inv(&self) -> Self {
+        assert!(!self.is_zero(), "division by zero in GF({})", P);
+        // Fermatの小定理
+        self.pow(P - 2)
+    }
+}
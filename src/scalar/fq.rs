@@ -0,0 +1,425 @@
+use crate::scalar::{univariate, Field, Fp, Rational, Ring};
+
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::sync::Arc;
+
+// 位数がこれ以下の体では対数表を使う
+const LOG_TABLE_LIMIT: u64 = 1 << 16;
+
+// Conway 多項式 (係数は次数の昇順)
+const CONWAY_POLYNOMIALS: &[(u64, &[u64])] = &[
+    (2, &[1, 1]),
+    (2, &[1, 1, 1]),
+    (2, &[1, 1, 0, 1]),
+    (2, &[1, 1, 0, 0, 1]),
+    (2, &[1, 0, 1, 0, 0, 1]),
+    (2, &[1, 1, 0, 1, 1, 0, 1]),
+    (2, &[1, 1, 0, 0, 0, 0, 0, 1]),
+    (2, &[1, 0, 1, 1, 1, 0, 0, 0, 1]),
+    (2, &[1, 0, 0, 0, 1, 0, 0, 0, 0, 1]),
+    (2, &[1, 1, 1, 1, 0, 1, 1, 0, 0, 0, 1]),
+    (2, &[1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, &[1, 1, 0, 1, 0, 1, 1, 1, 0, 0, 0, 0, 1]),
+    (2, &[1, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, &[1, 0, 0, 1, 0, 1, 0, 1, 0, 0, 0, 0, 0, 0, 1]),
+    (2, &[1, 0, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, &[1, 0, 1, 1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (3, &[1, 1]),
+    (3, &[2, 2, 1]),
+    (3, &[1, 2, 0, 1]),
+    (3, &[2, 0, 0, 2, 1]),
+    (3, &[1, 2, 0, 0, 0, 1]),
+    (3, &[2, 2, 1, 0, 2, 0, 1]),
+    (5, &[3, 1]),
+    (5, &[2, 4, 1]),
+    (5, &[3, 3, 0, 1]),
+    (5, &[2, 4, 4, 0, 1]),
+    (7, &[4, 1]),
+    (7, &[3, 6, 1]),
+    (7, &[4, 0, 6, 1]),
+    (7, &[3, 4, 5, 0, 1]),
+];
+
+// 原始元 g について exp[i] = g^i, log[x] = i (元は係数を P 進数とみた整数で表す)
+#[derive(Debug)]
+struct LogTables {
+    exp: Vec<u32>,
+    log: Vec<u32>,
+}
+
+/// 有限体 GF(P^k) = GF(P)[t]/(m(t)) (m は GF(P) 上既約)
+#[derive(Debug)]
+pub struct FiniteField<const P: u64> {
+    // 定義多項式 (モニック, 係数は次数の昇順)
+    modulus: Vec<Fp<P>>,
+    name: String,
+    tables: Option<LogTables>,
+}
+
+impl<const P: u64> PartialEq for FiniteField<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.modulus == other.modulus
+    }
+}
+
+impl<const P: u64> FiniteField<P> {
+    /// 定義多項式 m の係数を次数の昇順で与える (既約性は呼び出し側が保証する)
+    pub fn new(modulus: Vec<Fp<P>>, name: &str) -> Arc<Self> {
+        let m = univariate::trim(modulus);
+        assert!(m.len() >= 2, "modulus must have positive degree");
+
+        let lc_inv = m[m.len() - 1].inv();
+        let mut field = Self {
+            modulus: m.into_iter().map(|c| c * lc_inv).collect(),
+            name: name.to_string(),
+            tables: None,
+        };
+        if let Some(q) = field.order() {
+            if q <= LOG_TABLE_LIMIT {
+                field.tables = Some(field.build_tables(q));
+            }
+        }
+        Arc::new(field)
+    }
+
+    /// Conway 多項式で定義した GF(P^k) (表に無ければ None)
+    pub fn conway(k: usize, name: &str) -> Option<Arc<Self>> {
+        CONWAY_POLYNOMIALS
+            .iter()
+            .find(|(p, m)| *p == P && m.len() == k + 1)
+            .map(|(_, m)| Self::new(m.iter().map(|&c| Fp::from(c)).collect(), name))
+    }
+
+    /// 拡大次数 k
+    pub fn degree(&self) -> usize {
+        self.modulus.len() - 1
+    }
+
+    /// 位数 P^k (u64 に収まらなければ None)
+    pub fn order(&self) -> Option<u64> {
+        (0..self.degree()).try_fold(1u64, |q, _| q.checked_mul(P))
+    }
+
+    pub fn modulus(&self) -> &[Fp<P>] {
+        &self.modulus
+    }
+
+    /// 生成元 t
+    pub fn generator(self: &Arc<Self>) -> Fq<P> {
+        self.element(vec![Fp::zero(), Fp::one()])
+    }
+
+    /// c_0 + c_1 t + c_2 t^2 + ...
+    pub fn element(self: &Arc<Self>, coeffs: Vec<Fp<P>>) -> Fq<P> {
+        Fq::reduce(coeffs, Some(self.clone()))
+    }
+
+    fn mul_mod(&self, a: &[Fp<P>], b: &[Fp<P>]) -> Vec<Fp<P>> {
+        let c = univariate::mul(a, b);
+        if c.len() > self.degree() {
+            univariate::div_rem(&c, &self.modulus).1
+        } else {
+            c
+        }
+    }
+
+    fn pow_mod(&self, a: &[Fp<P>], e: u64) -> Vec<Fp<P>> {
+        let mut ret = vec![Fp::one()];
+        let mut base = a.to_vec();
+        let mut e = e;
+        while e > 0 {
+            if e & 1 == 1 {
+                ret = self.mul_mod(&ret, &base);
+            }
+            base = self.mul_mod(&base, &base);
+            e >>= 1;
+        }
+        ret
+    }
+
+    fn to_index(a: &[Fp<P>]) -> usize {
+        a.iter()
+            .rev()
+            .fold(0, |index, c| index * P as usize + c.value() as usize)
+    }
+
+    fn from_index(index: usize) -> Vec<Fp<P>> {
+        let mut index = index;
+        let mut ret = Vec::new();
+        while index > 0 {
+            ret.push(Fp::from(index as u64 % P));
+            index /= P as usize;
+        }
+        ret
+    }
+
+    fn build_tables(&self, q: u64) -> LogTables {
+        // q - 1 の素因数
+        let mut primes = Vec::new();
+        let mut r = q - 1;
+        let mut d = 2;
+        while d * d <= r {
+            if r.is_multiple_of(d) {
+                primes.push(d);
+                while r.is_multiple_of(d) {
+                    r /= d;
+                }
+            }
+            d += 1;
+        }
+        if r > 1 {
+            primes.push(r);
+        }
+
+        // 位数が q - 1 の元を探す
+        let one = vec![Fp::one()];
+        let g = (1..q as usize)
+            .map(Self::from_index)
+            .find(|g| {
+                self.pow_mod(g, q - 1) == one
+                    && primes.iter().all(|r| self.pow_mod(g, (q - 1) / r) != one)
+            })
+            .expect("modulus is not irreducible");
+
+        let mut exp = Vec::with_capacity(q as usize - 1);
+        let mut log = vec![0; q as usize];
+        let mut x = one;
+        for i in 0..(q - 1) {
+            let index = Self::to_index(&x);
+            exp.push(index as u32);
+            log[index] = i as u32;
+            x = self.mul_mod(&x, &g);
+        }
+
+        LogTables { exp, log }
+    }
+}
+
+/// 有限体 GF(P^k) の元
+///
+/// GF(P) の元は体を持たない定数として表し、演算時に相手の体に属するものとして扱う。
+#[derive(Clone)]
+pub struct Fq<const P: u64> {
+    // 係数は次数の昇順 (次数は定義多項式の次数未満)
+    coeffs: Vec<Fp<P>>,
+    field: Option<Arc<FiniteField<P>>>,
+}
+
+impl<const P: u64> Fq<P> {
+    fn reduce(coeffs: Vec<Fp<P>>, field: Option<Arc<FiniteField<P>>>) -> Self {
+        let coeffs = match &field {
+            Some(field) if coeffs.len() > field.degree() => {
+                univariate::div_rem(&coeffs, &field.modulus).1
+            }
+            _ => univariate::trim(coeffs),
+        };
+        Self { coeffs, field }
+    }
+
+    // 体を持たない定数はどの体の元とも比べられる
+    fn same_field(&self, other: &Self) -> bool {
+        match (&self.field, &other.field) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b) || a == b,
+            _ => true,
+        }
+    }
+
+    fn common_field(&self, other: &Self) -> Option<Arc<FiniteField<P>>> {
+        match (&self.field, &other.field) {
+            (Some(a), Some(_)) => {
+                assert!(self.same_field(other), "elements of different fields");
+                Some(a.clone())
+            }
+            (Some(a), None) => Some(a.clone()),
+            (None, b) => b.clone(),
+        }
+    }
+
+    /// 生成元に関する係数 (次数の昇順)
+    pub fn coeffs(&self) -> &[Fp<P>] {
+        &self.coeffs
+    }
+
+    pub fn field(&self) -> Option<&Arc<FiniteField<P>>> {
+        self.field.as_ref()
+    }
+}
+
+impl<const P: u64> From<Fp<P>> for Fq<P> {
+    fn from(c: Fp<P>) -> Self {
+        Self::reduce(vec![c], None)
+    }
+}
+
+impl<const P: u64> From<u64> for Fq<P> {
+    fn from(c: u64) -> Self {
+        Self::from(Fp::from(c))
+    }
+}
+
+impl<const P: u64> From<i64> for Fq<P> {
+    fn from(c: i64) -> Self {
+        Self::from(Fp::from(c))
+    }
+}
+
+/// 分母が P で割り切れる時は像が存在しないので失敗する
+impl<const P: u64> TryFrom<&Rational> for Fq<P> {
+    type Error = ();
+
+    fn try_from(v: &Rational) -> Result<Self, Self::Error> {
+        Fp::try_from(v).map(Self::from)
+    }
+}
+
+impl<const P: u64> PartialEq for Fq<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coeffs == other.coeffs && self.same_field(other)
+    }
+}
+
+impl<const P: u64> Display for Fq<P> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.coeffs.is_empty() {
+            return write!(f, "0");
+        }
+        let name = self.field.as_ref().map_or("t", |field| &field.name);
+
+        let mut terms = Vec::new();
+        for (i, c) in self.coeffs.iter().enumerate().rev() {
+            if c.is_zero() {
+                continue;
+            }
+            let c = c.value();
+            let mut term = String::new();
+            if i == 0 || c != 1 {
+                term.push_str(&c.to_string());
+                if i > 0 {
+                    term.push('*');
+                }
+            }
+            match i {
+                0 => {}
+                1 => term.push_str(name),
+                _ => term.push_str(&format!("{}^{}", name, i)),
+            }
+            terms.push(term);
+        }
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+impl<const P: u64> Debug for Fq<P> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        Display::fmt(self, f)
+    }
+}
+
+impl<const P: u64> Add for Fq<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let field = self.common_field(&other);
+        Self::reduce(univariate::add(&self.coeffs, &other.coeffs), field)
+    }
+}
+
+impl<const P: u64> Sub for Fq<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let field = self.common_field(&other);
+        Self::reduce(univariate::sub(&self.coeffs, &other.coeffs), field)
+    }
+}
+
+impl<const P: u64> Mul for Fq<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let field = self.common_field(&other);
+        if self.is_zero() || other.is_zero() {
+            return Self {
+                coeffs: Vec::new(),
+                field,
+            };
+        }
+
+        match field.as_ref().and_then(|field| field.tables.as_ref()) {
+            Some(tables) => {
+                let field = field.as_ref().unwrap();
+                let l = tables.log[FiniteField::to_index(&self.coeffs)] as usize
+                    + tables.log[FiniteField::to_index(&other.coeffs)] as usize;
+                let index = tables.exp[l % tables.exp.len()] as usize;
+                Self {
+                    coeffs: FiniteField::from_index(index),
+                    field: Some(field.clone()),
+                }
+            }
+            None => Self::reduce(univariate::mul(&self.coeffs, &other.coeffs), field),
+        }
+    }
+}
+
+impl<const P: u64> Div for Fq<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inv()
+    }
+}
+
+impl<const P: u64> Neg for Fq<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            coeffs: univariate::neg(&self.coeffs),
+            field: self.field,
+        }
+    }
+}
+
+impl<const P: u64> Ring for Fq<P> {
+    fn zero() -> Self {
+        Self {
+            coeffs: Vec::new(),
+            field: None,
+        }
+    }
+    fn one() -> Self {
+        Self::from(Fp::one())
+    }
+    fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+}
+
+impl<const P: u64> Field for Fq<P> {
+    fn inv(&self) -> Self {
+        assert!(!self.is_zero(), "division by zero in finite field");
+
+        // GF(P) の元
+        if self.coeffs.len() == 1 {
+            return Self {
+                coeffs: vec![self.coeffs[0].inv()],
+                field: self.field.clone(),
+            };
+        }
+
+        let field = self.field.as_ref().unwrap();
+        let coeffs = match &field.tables {
+            Some(tables) => {
+                let n = tables.exp.len();
+                let l = tables.log[FiniteField::to_index(&self.coeffs)] as usize;
+                FiniteField::from_index(tables.exp[(n - l) % n] as usize)
+            }
+            None => univariate::inverse_mod(&self.coeffs, &field.modulus)
+                .expect("modulus is not irreducible"),
+        };
+        Self::reduce(coeffs, self.field.clone())
+    }
+}
@@ -0,0 +1,224 @@
+use crate::monomial::{Monomial, MonomialOrder};
+use crate::polynomial::{self, Polynomial, PolynomialHandlers};
+use crate::scalar::{Field, Integer, Rational, Ring};
+
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// 有理関数体 Q(t_1, ..., t_k) の元
+///
+/// 分子と分母は既約で、分母の主係数は1に正規化する。
+/// 有理数はパラメータを持たない (k = 0 の) 定数として表し、演算時に相手のパラメータ環に持ち上げる。
+#[derive(Clone)]
+pub struct RationalFunction {
+    num: Polynomial<Rational>,
+    den: Polynomial<Rational>,
+}
+
+impl RationalFunction {
+    pub fn new(num: Polynomial<Rational>, den: Polynomial<Rational>) -> Self {
+        assert!(
+            den.fetch_lc().is_some(),
+            "division by zero rational function"
+        );
+        assert_eq!(num.get_n(), den.get_n());
+        assert_eq!(num.get_monomial_order(), den.get_monomial_order());
+
+        if num.fetch_lc().is_none() {
+            let (n, o) = (num.get_n(), num.get_monomial_order());
+            return Self {
+                num,
+                den: Polynomial::from((Rational::one(), n, o)),
+            };
+        }
+
+        let g = polynomial::gcd(&num, &den);
+        let (num, den) = (
+            polynomial::divide_exact(&num, &g),
+            polynomial::divide_exact(&den, &g),
+        );
+
+        let lc_inv = den.fetch_lc().unwrap().invert();
+        let c = Polynomial::from((lc_inv, num.get_n(), num.get_monomial_order()));
+        Self {
+            num: &num * &c,
+            den: &den * &c,
+        }
+    }
+
+    /// k 個のパラメータのうち i 番目 (0始まり) の t_{i+1}
+    pub fn parameter(i: usize, k: usize, monomial_order: MonomialOrder) -> Self {
+        let mut v = vec![0; k];
+        v[i] = 1;
+        Self::from(Polynomial::from((
+            Rational::one(),
+            Monomial::from((v, monomial_order.clone())),
+            monomial_order,
+        )))
+    }
+
+    pub fn numerator(&self) -> &Polynomial<Rational> {
+        &self.num
+    }
+
+    pub fn denominator(&self) -> &Polynomial<Rational> {
+        &self.den
+    }
+
+    // パラメータを持たない定数を n 変数に持ち上げる
+    fn lift(&self, n: usize, monomial_order: MonomialOrder) -> Self {
+        if self.num.get_n() == n {
+            return self.clone();
+        }
+        assert_eq!(
+            self.num.get_n(),
+            0,
+            "rational functions in different parameters"
+        );
+
+        let c = self.num.fetch_lc().unwrap_or_else(Rational::zero) / self.den.fetch_lc().unwrap();
+        Self {
+            num: Polynomial::from((c, n, monomial_order.clone())),
+            den: Polynomial::from((Rational::one(), n, monomial_order)),
+        }
+    }
+
+    fn unify(self, other: Self) -> (Self, Self) {
+        if self.num.get_n() >= other.num.get_n() {
+            let (n, o) = (self.num.get_n(), self.num.get_monomial_order());
+            (self, other.lift(n, o))
+        } else {
+            let (n, o) = (other.num.get_n(), other.num.get_monomial_order());
+            (self.lift(n, o), other)
+        }
+    }
+}
+
+impl From<Polynomial<Rational>> for RationalFunction {
+    fn from(num: Polynomial<Rational>) -> Self {
+        let den = Polynomial::from((Rational::one(), num.get_n(), num.get_monomial_order()));
+        Self { num, den }
+    }
+}
+
+impl From<Rational> for RationalFunction {
+    fn from(c: Rational) -> Self {
+        Self::from(Polynomial::from((c, 0, MonomialOrder::Lex)))
+    }
+}
+
+impl From<i64> for RationalFunction {
+    fn from(c: i64) -> Self {
+        Self::from(Rational::from(c))
+    }
+}
+
+impl PartialEq for RationalFunction {
+    fn eq(&self, other: &Self) -> bool {
+        let (lhs, rhs) = self.clone().unify(other.clone());
+        lhs.num == rhs.num && lhs.den == rhs.den
+    }
+}
+
+// 先頭の " - " を "-" にする
+fn to_string_with_parameter(f: &Polynomial<Rational>) -> String {
+    let s = f.to_string_with("t");
+    match s.strip_prefix(" - ") {
+        Some(s) => format!("-{}", s),
+        None => s,
+    }
+}
+
+impl Display for RationalFunction {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let num = to_string_with_parameter(&self.num);
+        if self.den.fetch_total_degree() == Some(Integer::zero()) {
+            return write!(f, "{}", num);
+        }
+
+        let den = to_string_with_parameter(&self.den);
+        let wrap = |s: String| {
+            if s.contains([' ', '/']) {
+                format!("({})", s)
+            } else {
+                s
+            }
+        };
+        write!(f, "{}/{}", wrap(num), wrap(den))
+    }
+}
+
+impl Debug for RationalFunction {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        Display::fmt(self, f)
+    }
+}
+
+impl Add for RationalFunction {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (lhs, rhs) = self.unify(other);
+        Self::new(
+            &lhs.num * &rhs.den + &rhs.num * &lhs.den,
+            &lhs.den * &rhs.den,
+        )
+    }
+}
+
+impl Sub for RationalFunction {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl Mul for RationalFunction {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (lhs, rhs) = self.unify(other);
+        Self::new(&lhs.num * &rhs.num, &lhs.den * &rhs.den)
+    }
+}
+
+impl Div for RationalFunction {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inv()
+    }
+}
+
+impl Neg for RationalFunction {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        // 分子の符号を変えるだけで既約性も分母の正規化も保たれる
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Ring for RationalFunction {
+    fn zero() -> Self {
+        Self::from(Rational::zero())
+    }
+    fn one() -> Self {
+        Self::from(Rational::one())
+    }
+    fn is_zero(&self) -> bool {
+        self.num.fetch_lc().is_none()
+    }
+}
+
+impl Field for RationalFunction {
+    fn inv(&self) -> Self {
+        assert!(!self.is_zero(), "division by zero rational function");
+        Self::new(self.den.clone(), self.num.clone())
+    }
+}
@@ -0,0 +1,327 @@
+#[cfg(test)]
+#[allow(unused_imports)]
+use super::{
+    AlgebraicNumber, BigInt, BigRational, Field, FiniteField, Fp, Fq, Integer, NumberField,
+    Rational, RationalFunction, Ring,
+};
+#[allow(unused_imports)]
+use std::convert::TryFrom;
+
+#[test]
+fn test_fp_arithmetic() {
+    type F = Fp<1_000_000_007>;
+
+    let a = F::from(123_456_789u64);
+    let b = F::from(-987_654_321i64);
+
+    assert_eq!((a + b).value(), 135_802_475);
+    assert_eq!((a - a), F::zero());
+    assert_eq!((a * b).value(), 740_893_148);
+    assert_eq!(a * a.inv(), F::one());
+    assert_eq!(b / b, F::one());
+    assert_eq!(-F::one(), F::from(1_000_000_006u64));
+    assert_eq!(F::one().to_string(), "1");
+}
+
+#[test]
+fn test_fp_large_prime() {
+    // 2^61 - 1
+    type F = Fp<2_305_843_009_213_693_951>;
+
+    let a = F::from(2_305_843_009_213_693_950u64);
+    assert_eq!(a * a, F::one());
+    assert_eq!(a + F::one(), F::zero());
+
+    let b = F::from(1u64 << 40);
+    assert_eq!(b * b, F::from(1u64 << 19));
+    assert_eq!(b * b.inv(), F::one());
+}
+
+#[test]
+fn test_fp_characteristic_two() {
+    type F = Fp<2>;
+
+    let one = F::one();
+    assert_eq!(one + one, F::zero());
+    assert_eq!(-one, one);
+    assert_eq!(one.inv(), one);
+    assert_eq!(F::from(-3i64), one);
+}
+
+#[test]
+fn test_fp_from_rational() {
+    type F = Fp<7>;
+
+    let x = F::try_from(&(Rational::from(3) / Rational::from(5))).unwrap();
+    assert_eq!(x * F::from(5u64), F::from(3u64));
+
+    let x = F::try_from(&(Rational::from(-1) / Rational::from(2))).unwrap();
+    assert_eq!(x, F::from(3u64));
+
+    assert!(F::try_from(&(Rational::from(1) / Rational::from(14))).is_err());
+    assert_eq!(F::from(&Integer::from(-8)), F::from(6u64));
+}
+
+#[test]
+fn test_crt() {
+    let m = Integer::from(7);
+    let n = Integer::from(11);
+
+    let x = super::crt(&Integer::from(3), &m, &Integer::from(-2), &n);
+    assert_eq!(x, Integer::from(31));
+
+    let x = super::crt(&Integer::from(0), &m, &Integer::from(0), &n);
+    assert_eq!(x, Integer::from(0));
+}
+
+#[test]
+fn test_rational_reconstruction() {
+    let m = Integer::from(1_000_000_007);
+
+    // -3/7 mod m
+    let a = super::modulo(&(Integer::from(-3) * Integer::from(142_857_144)), &m);
+    assert_eq!(
+        super::rational_reconstruction(&a, &m),
+        Some(Rational::from(-3) / Rational::from(7))
+    );
+
+    assert_eq!(
+        super::rational_reconstruction(&Integer::from(0), &m),
+        Some(Rational::from(0))
+    );
+
+    // 分母が sqrt(m / 2) を超える 12345/67891 は復元できず、条件を満たす別の分数になる
+    let a = super::modulo(&(Integer::from(12_345) * Integer::from(558_115_218)), &m);
+    assert_eq!(
+        super::rational_reconstruction(&a, &m),
+        Some(Rational::from(17_177) / Rational::from(13_460))
+    );
+}
+
+#[test]
+fn test_algebraic_number() {
+    // Q(sqrt 2)
+    let field = NumberField::new(
+        vec![Rational::from(-2), Rational::zero(), Rational::one()],
+        "a",
+    );
+    let a = field.generator();
+
+    assert_eq!(a.clone() * a.clone(), AlgebraicNumber::from(2));
+    assert_eq!(a.clone() * a.inv(), AlgebraicNumber::one());
+
+    // (1 + a)^{-1} = a - 1
+    let b = a.clone() + AlgebraicNumber::one();
+    assert_eq!(b.inv(), a.clone() - AlgebraicNumber::one());
+    assert_eq!(b.inv().to_string(), "a - 1");
+    assert_eq!((-b).to_string(), "-a - 1");
+
+    // Q(a), a^3 = a + 1
+    let field = NumberField::new(
+        vec![
+            -Rational::one(),
+            -Rational::one(),
+            Rational::zero(),
+            Rational::one(),
+        ],
+        "b",
+    );
+    let c = field.element(vec![
+        Rational::from(3),
+        Rational::from(-1),
+        Rational::from(2),
+    ]);
+    assert_eq!(c.clone() * c.inv(), AlgebraicNumber::one());
+    assert_eq!(c.clone() / c.clone(), AlgebraicNumber::one());
+    assert_eq!(c.to_string(), "2*b^2 - b + 3");
+
+    // 係数が同じでも体が違えば等しくない
+    let other = NumberField::new(
+        vec![Rational::from(-3), Rational::zero(), Rational::one()],
+        "a",
+    );
+    assert_ne!(a, other.generator());
+    assert_eq!(
+        other.element(vec![Rational::from(2)]),
+        AlgebraicNumber::from(2)
+    );
+    let same = NumberField::new(
+        vec![Rational::from(-2), Rational::zero(), Rational::one()],
+        "a",
+    );
+    assert_eq!(a, same.generator());
+}
+
+#[test]
+fn test_rational_function() {
+    use crate::monomial::MonomialOrder;
+
+    let t1 = RationalFunction::parameter(0, 2, MonomialOrder::Lex);
+    let t2 = RationalFunction::parameter(1, 2, MonomialOrder::Lex);
+    let one = RationalFunction::one();
+
+    // (t1^2 - t2^2) / (t1 - t2) = t1 + t2
+    let a = t1.clone() * t1.clone() - t2.clone() * t2.clone();
+    assert_eq!(a / (t1.clone() - t2.clone()), t1.clone() + t2.clone());
+
+    // 1 / (t1 - 1) + 1 / (t1 + 1) = 2 t1 / (t1^2 - 1)
+    let b = one.clone() / (t1.clone() - one.clone()) + one.clone() / (t1.clone() + one.clone());
+    assert_eq!(
+        b,
+        RationalFunction::from(2) * t1.clone() / (t1.clone() * t1.clone() - one.clone())
+    );
+    assert_eq!(b.to_string(), "2(t_1)/((t_1)^2 - 1)");
+
+    assert_eq!(b.clone() * b.inv(), one);
+    assert!((b.clone() - b).is_zero());
+    assert_eq!(
+        t2.clone() / RationalFunction::from(2),
+        t2 * RationalFunction::from(Rational::from(1) / Rational::from(2))
+    );
+}
+
+#[test]
+fn test_finite_field() {
+    // GF(2^8) (AES の定義多項式 t^8 + t^4 + t^3 + t + 1, t は原始元でない)
+    let field = FiniteField::<2>::new(
+        [1, 1, 0, 1, 1, 0, 0, 0, 1]
+            .iter()
+            .map(|&c| Fp::from(c as u64))
+            .collect(),
+        "t",
+    );
+    let byte = |b: u64| field.element((0..8).map(|i| Fp::from((b >> i) & 1)).collect());
+
+    assert_eq!(byte(0x53) * byte(0xca), Fq::one());
+    assert_eq!(byte(0x53).inv(), byte(0xca));
+    assert_eq!(byte(0x57) * byte(0x83), byte(0xc1));
+    assert_eq!(byte(0x57) + byte(0x83), byte(0xd4));
+    assert_eq!(byte(0x57) - byte(0x57), Fq::zero());
+    assert_eq!(byte(0x13).to_string(), "t^4 + t + 1");
+
+    // GF(5^3) (Conway 多項式 t^3 + 3t + 3)
+    let field = FiniteField::<5>::conway(3, "a").unwrap();
+    let a = field.generator();
+    assert_eq!(
+        a.clone() * a.clone() * a.clone(),
+        Fq::from(2u64) * a.clone() + Fq::from(2u64)
+    );
+    let b = a.clone() * a.clone() + Fq::from(4u64);
+    assert_eq!(b.clone() * b.inv(), Fq::one());
+    assert_eq!(b.to_string(), "a^2 + 4");
+    assert!(FiniteField::<5>::conway(20, "a").is_none());
+
+    // 係数が同じでも体が違えば等しくない
+    let other = FiniteField::<5>::new(
+        [1, 1, 0, 1].iter().map(|&c| Fp::from(c as u64)).collect(),
+        "a",
+    );
+    assert_ne!(a, other.generator());
+    assert_eq!(other.element(vec![Fp::from(4u64)]), Fq::from(4u64));
+    let same = FiniteField::<5>::new(
+        [3, 3, 0, 1].iter().map(|&c| Fp::from(c as u64)).collect(),
+        "a",
+    );
+    assert_eq!(a, same.generator());
+
+    // GF(P^2) (P が大きく対数表を使わない)
+    type F = Fp<1_000_000_007>;
+    let field = FiniteField::new(vec![F::one(), F::zero(), F::one()], "i");
+    let i = field.generator();
+    assert_eq!(i.clone() * i.clone(), -Fq::one());
+    let c = i.clone() + Fq::from(3u64);
+    assert_eq!(c.clone() / c.clone(), Fq::one());
+}
+
+#[test]
+fn test_bigint() {
+    let a = BigInt::from(2).pow(200) + BigInt::from(12345);
+    let b = -(BigInt::from(3).pow(70) + BigInt::from(7));
+
+    assert_eq!(
+        (&a * &b).to_string(),
+        "-4022415811669964826000051984469597717876026960536901975226023554114041431208058330688600830176"
+    );
+    assert_eq!((&a / &b).to_string(), "-641964928288915446533058609");
+    assert_eq!((&a % &b).to_string(), "1182506464160522740308723081379417");
+    assert_eq!(&(&(&a / &b) * &b) + &(&a % &b), a);
+
+    // 剰余の符号は被除数に従う
+    let c = -(BigInt::from(10).pow(40) + BigInt::from(1));
+    let d = BigInt::from(u64::MAX);
+    assert_eq!((&c / &d).to_string(), "-542101086242752217033");
+    assert_eq!((&c % &d).to_string(), "-2098486950404341706");
+
+    assert_eq!(
+        "-123456789012345678901234567890"
+            .parse::<BigInt>()
+            .unwrap()
+            .to_string(),
+        "-123456789012345678901234567890"
+    );
+    assert_eq!(BigInt::from(-12).gcd(&BigInt::from(18)), BigInt::from(6));
+    assert_eq!(Option::<u64>::from(&d), Some(u64::MAX));
+    assert_eq!(Option::<u64>::from(&(&d + &BigInt::one())), None);
+    assert_eq!(Option::<i64>::from(&BigInt::from(i64::MIN)), Some(i64::MIN));
+    assert!(c < b && b < BigInt::zero() && BigInt::zero() < a);
+}
+
+#[test]
+fn test_bigrational() {
+    let a = BigRational::ratio(&BigInt::from(6), &BigInt::from(-4));
+    assert_eq!(a.to_string(), "-3/2");
+    assert_eq!(a.get_num(), BigInt::from(-3));
+    assert_eq!(a.get_den(), BigInt::from(2));
+    assert_eq!("6/-4".parse::<BigRational>(), Ok(a.clone()));
+    assert!("1/0".parse::<BigRational>().is_err());
+    assert!("1/".parse::<BigRational>().is_err());
+
+    let b = BigRational::from(1) / BigRational::from(3);
+    assert_eq!((&a + &b).to_string(), "-7/6");
+    assert_eq!((&a * &b).to_string(), "-1/2");
+    assert_eq!(a.invert().to_string(), "-2/3");
+    assert_eq!((&a - &a), BigRational::zero());
+    assert_eq!(a.floor(), BigInt::from(-2));
+    assert!(a < b);
+}
+
+// GMP と同じ結果になるか
+#[cfg(feature = "gmp")]
+#[test]
+fn test_bigint_against_gmp() {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for i in 0..200 {
+        let s = next() >> 1;
+        let (mut x, mut y) = (BigInt::from(s), Integer::from(s));
+        for _ in 0..(i % 7) {
+            let d = next();
+            x = &x * &BigInt::from(u64::MAX) + BigInt::from(d);
+            y = &y * &Integer::from(u64::MAX) + Integer::from(d);
+        }
+        let s = next() | 1;
+        let (mut z, mut w) = (BigInt::from(s), Integer::from(s));
+        for _ in 0..(i % 4) {
+            let (d, e) = (next(), next() >> 2);
+            z = &z * &BigInt::from(d) - BigInt::from(e);
+            w = &w * &Integer::from(d) - Integer::from(e);
+        }
+        if i % 3 == 0 {
+            x = -x;
+            y = -y;
+        }
+
+        assert_eq!(x.to_string(), y.to_string());
+        assert_eq!(z.to_string(), w.to_string());
+        assert_eq!((&x / &z).to_string(), (&y / &w).to_string());
+        assert_eq!((&x % &z).to_string(), (&y % &w).to_string());
+        assert_eq!((&x * &z).to_string(), (&y * &w).to_string());
+        assert_eq!((&x - &z).to_string(), (&y - &w).to_string());
+    }
+}
@@ -0,0 +1,90 @@
+// 係数を次数の昇順に並べた1変数多項式の演算 (係数体の実装用)
+use crate::scalar::{Field, Ring};
+
+pub(crate) fn trim<K: Ring>(mut a: Vec<K>) -> Vec<K> {
+    while a.last().is_some_and(|c| c.is_zero()) {
+        a.pop();
+    }
+    a
+}
+
+pub(crate) fn add<K: Ring>(a: &[K], b: &[K]) -> Vec<K> {
+    let n = std::cmp::max(a.len(), b.len());
+    let ret = (0..n)
+        .map(|i| match (a.get(i), b.get(i)) {
+            (Some(ai), Some(bi)) => ai.clone() + bi.clone(),
+            (Some(ai), None) => ai.clone(),
+            (None, Some(bi)) => bi.clone(),
+            (None, None) => unreachable!(),
+        })
+        .collect();
+    trim(ret)
+}
+
+pub(crate) fn neg<K: Ring>(a: &[K]) -> Vec<K> {
+    a.iter().map(|c| -c.clone()).collect()
+}
+
+pub(crate) fn sub<K: Ring>(a: &[K], b: &[K]) -> Vec<K> {
+    add(a, &neg(b))
+}
+
+pub(crate) fn mul<K: Ring>(a: &[K], b: &[K]) -> Vec<K> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut ret = vec![K::zero(); a.len() + b.len() - 1];
+    for (i, ai) in a.iter().enumerate() {
+        for (j, bj) in b.iter().enumerate() {
+            ret[i + j] = ret[i + j].clone() + ai.clone() * bj.clone();
+        }
+    }
+    trim(ret)
+}
+
+/// (商, 余り)
+pub(crate) fn div_rem<K: Field>(a: &[K], b: &[K]) -> (Vec<K>, Vec<K>) {
+    assert!(!b.is_empty(), "division by zero polynomial");
+
+    let lc_inv = b[b.len() - 1].inv();
+    let mut r = trim(a.to_vec());
+    if r.len() < b.len() {
+        return (Vec::new(), r);
+    }
+
+    let mut q = vec![K::zero(); r.len() - b.len() + 1];
+    while r.len() >= b.len() {
+        let shift = r.len() - b.len();
+        let c = r[r.len() - 1].clone() * lc_inv.clone();
+        for (i, bi) in b.iter().enumerate() {
+            r[shift + i] = r[shift + i].clone() - c.clone() * bi.clone();
+        }
+        q[shift] = c;
+        r.pop();
+        r = trim(r);
+    }
+
+    (trim(q), r)
+}
+
+/// 拡張ユークリッド互除法で a の m を法とした逆元を求める (gcd(a, m) = 1 でなければ None)
+pub(crate) fn inverse_mod<K: Field>(a: &[K], m: &[K]) -> Option<Vec<K>> {
+    let (mut r0, mut r1) = (trim(m.to_vec()), div_rem(a, m).1);
+    let (mut s0, mut s1) = (Vec::new(), vec![K::one()]);
+
+    while !r1.is_empty() {
+        let (q, r2) = div_rem(&r0, &r1);
+        let s2 = sub(&s0, &mul(&q, &s1));
+        r0 = r1;
+        r1 = r2;
+        s0 = s1;
+        s1 = s2;
+    }
+
+    // r0 は定数でなければならない
+    if r0.len() != 1 {
+        return None;
+    }
+    let c = r0[0].inv();
+    Some(s0.into_iter().map(|si| si * c.clone()).collect())
+}